@@ -1,6 +1,6 @@
 #!/bin/sh -ex
 
-FEATURES="bitcoin_hashes global-context lowmemory use-rand rand-std recovery use-serde"
+FEATURES="alloc bitcoin_hashes global-context lowmemory use-rand rand-std recovery use-serde"
 
 # Use toolchain if explicitly specified
 if [ -n "$TOOLCHAIN" ]
@@ -39,6 +39,8 @@ if [ "$DO_FEATURE_MATRIX" = true ]; then
     RUSTFLAGS='--cfg=rust_secp_fuzz' RUSTDOCFLAGS=$RUSTFLAGS cargo test --all --features="$FEATURES"
     cargo test --all --features="rand rand-std"
     cargo test --all --features="rand serde"
+    # Without rand, e.g. for SurjectionProof::new_with_seed
+    cargo test --all --no-default-features --features="std bitcoin_hashes"
 
     if [ "$DO_BENCH" = true ]; then  # proxy for us having a nightly compiler
         cargo test --all --all-features
@@ -46,6 +48,14 @@ if [ "$DO_FEATURE_MATRIX" = true ]; then
     fi
 fi
 
+# Build the no_std test, which runs without an allocator
+if [ "$DO_NO_STD" = true ]; then
+    (
+        cd no_std_test
+        cargo run --release | grep -q "Verified Successfully"
+    )
+fi
+
 # Docs
 if [ "$DO_DOCS" = true ]; then
     cargo doc --all --features="$FEATURES"
@@ -0,0 +1,167 @@
+//! # secp256k1-zkp no-std test.
+//!
+//! This binary is built without libstd and without a global allocator. It checks that:
+//!     1. The range proof, surjection proof and musig APIs work on a context created on the stack.
+//!     2. The sources of `secp256k1-zkp` don't pull in libstd or liballoc without the `std` and `alloc`
+//!        features. They are built as a library of this crate, see `Cargo.toml`.
+//!
+//! The proofs were created from the fixed inputs below with the `std` feature, since creating them
+//! requires an allocator. Run it with `cargo run --release` from this directory, it prints
+//! "Verified Successfully!" on success and aborts otherwise.
+
+#![no_std]
+#![no_main]
+
+extern crate libc;
+extern crate secp256k1_zkp;
+
+use core::fmt::{self, Write};
+use core::panic::PanicInfo;
+
+use secp256k1_zkp::ffi::types::AlignedType;
+use secp256k1_zkp::*;
+
+/// A range proof for the value 42 with 8 private bits, created with `RangeProofBuilder::confidential`
+/// with value blinding factor `[1; 32]`, asset tag `[4; 32]`, generator blinding factor `[2; 32]` and
+/// nonce `[3; 32]`.
+const RANGE_PROOF: &str = concat!(
+    "4007027463a9ac56c5dca6da85cdfc41619182a0bc212e33ace9f62e43f63089bc32c75149a49dc3fd073961",
+    "b60e8489230980e7acfda36796b5d790909a183e3881742940ae6407ea7f0427d3cbf096e532012a59ee6995",
+    "bbf8ddcd12bc67076f0ab54d708ba5974f22af9c68b9588b344ee19d60b5b650c2f2acac33851042ff555606",
+    "53fefcf33943ea54e1dae54798cc911a37aa151674c242a5b8e332b9ecffff835e41220c813cf3dcad9e5091",
+    "af0569c911298b7738b832e09e74d8ce81dcc47229b9b3770b678dd4431bbcec219e0cd85bec7b49f071e583",
+    "0471de038bdda9729e2b796ccc267fae41725127badc7755f1f29d459b3e090a868b82067bba75a643354595",
+    "466ea120303cde0a480b5280738d21024c67c1c52ee19aef34eee3697e8499e57d36470bee6121f21ee75d1b",
+    "152f16e1ecd4515153da3003a2c4dc0fe41a039f818dde33dda1f317f063fb537501edc5622900cf39b8aacb",
+    "717c80569a1cd322b9053c3417e9eb5eb485786483cfcf338f1bae949689858ef61e8b03521df78332b5adcf",
+    "f4b0827aeb6dd715e408f75715aeb561da1131db21c4447f6112d2156d7faf6b5d7f7eacd17719cececfee26",
+    "25526b3753eb168e07d4e9fc6c39a9b0791ab43dbf38743a6e0a2224a97a0c961b4124dc1da4b559d437d8bf",
+    "0795af25f37ee3ee6c837340c05a9a8ddf842a8bc674b5f56693ecee2650140038e177f6fe0d26afde0bb604",
+    "9f8016309c6a4f4a4b9e526371f62470318ff3b841ca7b662b03eae20decf7301b37c28a0560b500aa2c489b",
+    "026183c704798553a161b32a1f3a0b36f287b5645a592dd6737a8985682969fdce44c2561ba3cc7ca75ed2c1",
+    "600372154e9f4b535baefe135848b3a317dfa736583e5b2b047df9",
+);
+
+/// A surjection proof for the asset tag `[6; 32]` blinded with `[9; 32]`, with the inputs `[5; 32]`
+/// blinded with `[7; 32]` and `[6; 32]` blinded with `[8; 32]`, created with the seed `[10; 32]`.
+const SURJECTION_PROOF: &str = concat!(
+    "0200033ad62f4cc5bc8e48dbc63a11f1cc4742601b52859da1986abf0349ba5c949a9fd73881357a17e61675",
+    "1b4a974b3f7f15ab9d85c28d34bbb760762f8bd3ece43a5e06ba5da8f5e09f80cd7722d443a99fcf4357a417",
+    "0c3464117818cce065c83e",
+);
+
+fn tweak(byte: u8) -> Tweak {
+    Tweak::from_slice(&[byte; 32]).unwrap()
+}
+
+fn from_hex(hex: &str, out: &mut [u8]) -> usize {
+    fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex character"),
+        }
+    }
+
+    let hex = hex.as_bytes();
+    for (i, pair) in hex.chunks(2).enumerate() {
+        out[i] = nibble(pair[0]) << 4 | nibble(pair[1]);
+    }
+    hex.len() / 2
+}
+
+#[no_mangle]
+pub extern "C" fn main(_argc: libc::c_int, _argv: *const *const libc::c_char) -> libc::c_int {
+    let mut buf = [AlignedType::ZERO; 64];
+    let size = Secp256k1::preallocate_size();
+    unsafe { libc::printf("needed size: %d\n\0".as_ptr() as _, size as libc::c_int) };
+
+    let secp = Secp256k1::preallocated_new(&mut buf).unwrap();
+
+    // Range proof
+    let generator = Generator::new_blinded(&secp, Tag::from([4; 32]), tweak(2));
+    let commitment = PedersenCommitment::new(&secp, 42, tweak(1), generator);
+    let other_commitment = PedersenCommitment::new(&secp, 43, tweak(1), generator);
+
+    let mut proof = [0u8; ffi::RANGEPROOF_MAX_LENGTH];
+    let len = from_hex(RANGE_PROOF, &mut proof);
+    let range = verify_range_proof(&secp, &proof[..len], commitment, &[], generator).unwrap();
+    assert_eq!(range.start, 0);
+    assert_eq!(range.end, 256);
+    assert!(verify_range_proof(&secp, &proof[..len], other_commitment, &[], generator).is_err());
+    assert!(verify_range_proof(&secp, &proof[..len], commitment, b"foo", generator).is_err());
+
+    // Surjection proof
+    let domain = [
+        Generator::new_blinded(&secp, Tag::from([5; 32]), tweak(7)),
+        Generator::new_blinded(&secp, Tag::from([6; 32]), tweak(8)),
+    ];
+    let codomain = Generator::new_blinded(&secp, Tag::from([6; 32]), tweak(9));
+
+    let mut proof = [0u8; 128];
+    let len = from_hex(SURJECTION_PROOF, &mut proof);
+    let proof = SurjectionProof::from_slice(&proof[..len]).unwrap();
+    assert_eq!(proof.n_total_inputs(), 2);
+    assert!(proof.verify(&secp, codomain, &domain));
+    assert!(!proof.verify(&secp, domain[0], &domain));
+
+    // Musig
+    let sec_key = SecretKey::from_slice(&[11; 32]).unwrap();
+    let msg = Message::from_slice(&[12; 32]).unwrap();
+    let (_sec_nonce, pub_nonce) =
+        new_musig_nonce_pair(&secp, [13; 32], None, Some(sec_key), Some(msg), None).unwrap();
+    let parsed = MusigPubNonce::from_slice(&pub_nonce.serialize()).unwrap();
+    assert_eq!(parsed, pub_nonce);
+    let (_, other_pub_nonce) =
+        new_musig_nonce_pair(&secp, [14; 32], None, Some(sec_key), Some(msg), None).unwrap();
+    assert_ne!(other_pub_nonce, pub_nonce);
+    assert!(new_musig_nonce_pair(&secp, [0; 32], None, None, None, None).is_err());
+
+    unsafe { libc::printf("Verified Successfully!\n\0".as_ptr() as _) };
+    0
+}
+
+const MAX_PRINT: usize = 511;
+struct Print {
+    loc: usize,
+    buf: [u8; 512],
+}
+
+impl Print {
+    pub fn new() -> Self {
+        Self {
+            loc: 0,
+            buf: [0u8; 512],
+        }
+    }
+
+    pub fn print(&self) {
+        unsafe {
+            libc::printf("%s\n\0".as_ptr() as _, self.buf.as_ptr());
+            libc::fflush(core::ptr::null_mut());
+        }
+    }
+}
+
+impl Write for Print {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let curr = self.loc;
+        if curr + s.len() > MAX_PRINT {
+            unsafe {
+                libc::printf("overflow\n\0".as_ptr() as _);
+                libc::abort();
+            }
+        }
+        self.loc += s.len();
+        self.buf[curr..self.loc].copy_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    let mut buf = Print::new();
+    write!(buf, "{}", info).unwrap();
+    buf.print();
+    unsafe { libc::abort() }
+}
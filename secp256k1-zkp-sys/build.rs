@@ -43,6 +43,7 @@ fn main() {
         .define("ENABLE_MODULE_GENERATOR", Some("1"))
         .define("ENABLE_MODULE_RANGEPROOF", Some("1"))
         .define("ENABLE_MODULE_ECDSA_ADAPTOR", Some("1"))
+        .define("ENABLE_MODULE_ECDSA_S2C", Some("1"))
         .define("ENABLE_MODULE_WHITELIST", Some("1"))
         .define("ENABLE_MODULE_EXTRAKEYS", Some("1"))
         .define("ENABLE_MODULE_MUSIG", Some("1"))
@@ -22,6 +22,8 @@
 #![deny(non_snake_case)]
 #![deny(unused_mut)]
 #![cfg_attr(all(not(test), not(feature = "std")), no_std)]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
 #[cfg(any(test, feature = "std"))]
 extern crate core;
 #[macro_use]
@@ -1,3 +1,5 @@
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, vec::Vec};
 use core::{fmt, hash};
 use {types::*, Context, KeyPair, NonceFn, PublicKey, Signature, XOnlyPublicKey};
 
@@ -8,6 +10,11 @@ pub const ECDSA_ADAPTOR_SIGNATURE_LENGTH: size_t = 162;
 /// The maximum number of whitelist keys.
 pub const WHITELIST_MAX_N_KEYS: size_t = 255;
 
+/// The maximum number of inputs that may be given in a surjection proof.
+pub const SURJECTIONPROOF_MAX_N_INPUTS: size_t = 256;
+/// The maximum number of inputs that may be used in a surjection proof.
+pub const SURJECTIONPROOF_MAX_USED_INPUTS: size_t = 256;
+
 extern "C" {
     #[cfg_attr(
         not(feature = "external-symbols"),
@@ -87,6 +94,20 @@ extern "C" {
         n_inputs: size_t,
     ) -> c_int;
 
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_pedersen_blind_sum"
+    )]
+    // Computes the sum of multiple positive and negative blinding factors.
+    // The first `npositive` factors are added, the remaining ones subtracted.
+    pub fn secp256k1_pedersen_blind_sum(
+        ctx: *const Context,
+        blind_out: *mut c_uchar,
+        blinds: *const *const c_uchar,
+        n: size_t,
+        npositive: size_t,
+    ) -> c_int;
+
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_pedersen_verify_tally"
@@ -101,7 +122,6 @@ extern "C" {
         ncnt: size_t,
     ) -> c_int;
 
-    #[cfg(feature = "std")]
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_rangeproof_info"
@@ -116,7 +136,6 @@ extern "C" {
         plen: size_t,
     ) -> c_int;
 
-    #[cfg(feature = "std")]
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_rangeproof_rewind"
@@ -138,7 +157,6 @@ extern "C" {
         gen: *const PublicKey,
     ) -> c_int;
 
-    #[cfg(feature = "std")]
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_rangeproof_verify"
@@ -155,7 +173,6 @@ extern "C" {
         gen: *const PublicKey,
     ) -> c_int;
 
-    #[cfg(feature = "std")]
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_rangeproof_sign"
@@ -214,6 +231,24 @@ extern "C" {
         proof: *const SurjectionProof,
     ) -> size_t;
 
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_surjectionproof_n_total_inputs"
+    )]
+    pub fn secp256k1_surjectionproof_n_total_inputs(
+        ctx: *const Context,
+        proof: *const SurjectionProof,
+    ) -> size_t;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_surjectionproof_n_used_inputs"
+    )]
+    pub fn secp256k1_surjectionproof_n_used_inputs(
+        ctx: *const Context,
+        proof: *const SurjectionProof,
+    ) -> size_t;
+
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_surjectionproof_parse"
@@ -338,6 +373,97 @@ extern "C" {
         enckey: *const PublicKey,
     ) -> c_int;
 
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_ecdsa_s2c_opening_parse"
+    )]
+    pub fn secp256k1_ecdsa_s2c_opening_parse(
+        cx: *const Context,
+        opening: *mut EcdsaS2cOpening,
+        input33: *const c_uchar,
+    ) -> c_int;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_ecdsa_s2c_opening_serialize"
+    )]
+    pub fn secp256k1_ecdsa_s2c_opening_serialize(
+        cx: *const Context,
+        output33: *mut c_uchar,
+        opening: *const EcdsaS2cOpening,
+    ) -> c_int;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_ecdsa_s2c_sign"
+    )]
+    pub fn secp256k1_ecdsa_s2c_sign(
+        cx: *const Context,
+        sig: *mut Signature,
+        s2c_opening: *mut EcdsaS2cOpening,
+        msg32: *const c_uchar,
+        seckey: *const c_uchar,
+        s2c_data32: *const c_uchar,
+    ) -> c_int;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_ecdsa_s2c_verify_commit"
+    )]
+    pub fn secp256k1_ecdsa_s2c_verify_commit(
+        cx: *const Context,
+        sig: *const Signature,
+        data32: *const c_uchar,
+        opening: *const EcdsaS2cOpening,
+    ) -> c_int;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_ecdsa_anti_exfil_host_commit"
+    )]
+    pub fn secp256k1_ecdsa_anti_exfil_host_commit(
+        cx: *const Context,
+        rand_commitment32: *mut c_uchar,
+        rand32: *const c_uchar,
+    ) -> c_int;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_ecdsa_anti_exfil_signer_commit"
+    )]
+    pub fn secp256k1_ecdsa_anti_exfil_signer_commit(
+        cx: *const Context,
+        s2c_opening: *mut EcdsaS2cOpening,
+        msg32: *const c_uchar,
+        seckey32: *const c_uchar,
+        rand_commitment32: *const c_uchar,
+    ) -> c_int;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_anti_exfil_sign"
+    )]
+    pub fn secp256k1_anti_exfil_sign(
+        cx: *const Context,
+        sig: *mut Signature,
+        msg32: *const c_uchar,
+        seckey: *const c_uchar,
+        host_data32: *const c_uchar,
+    ) -> c_int;
+
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_anti_exfil_host_verify"
+    )]
+    pub fn secp256k1_anti_exfil_host_verify(
+        cx: *const Context,
+        sig: *const Signature,
+        msg32: *const c_uchar,
+        pubkey: *const PublicKey,
+        host_data32: *const c_uchar,
+        opening: *const EcdsaS2cOpening,
+    ) -> c_int;
+
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_whitelist_signature_parse"
@@ -395,6 +521,16 @@ extern "C" {
         tweak32: *const c_uchar,
     ) -> c_int;
 
+    #[cfg_attr(
+        not(feature = "external-symbols"),
+        link_name = "rustsecp256k1zkp_v0_6_0_musig_pubkey_get"
+    )]
+    pub fn secp256k1_musig_pubkey_get(
+        cx: *const Context,
+        agg_pk: *mut PublicKey,
+        keyagg_cache: *mut MusigKeyaggCache,
+    ) -> c_int;
+
     #[cfg_attr(
         not(feature = "external-symbols"),
         link_name = "rustsecp256k1zkp_v0_6_0_whitelist_sign"
@@ -651,12 +787,12 @@ impl SurjectionProof {
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "alloc"))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RangeProof(Box<[c_uchar]>);
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl RangeProof {
     pub fn new(bytes: &[u8]) -> Self {
         RangeProof(bytes.into())
@@ -674,6 +810,10 @@ impl RangeProof {
         self.0.as_ptr()
     }
 
+    pub fn as_slice(&self) -> &[c_uchar] {
+        &self.0
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_vec()
     }
@@ -794,6 +934,19 @@ impl EcdsaAdaptorSignature {
     }
 }
 
+/// Sign-to-contract opening, i.e. the original public nonce of an ECDSA
+/// signature that commits to some data.
+#[repr(C)]
+pub struct EcdsaS2cOpening([c_uchar; 64]);
+impl_array_newtype!(EcdsaS2cOpening, c_uchar, 64);
+impl_raw_debug!(EcdsaS2cOpening);
+
+impl EcdsaS2cOpening {
+    pub fn new() -> Self {
+        EcdsaS2cOpening([0; 64])
+    }
+}
+
 #[repr(C)]
 pub struct ScratchSpace(c_int);
 
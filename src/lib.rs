@@ -27,6 +27,10 @@
 //! (as long as you are dependening on the correct version) which means [`SecretKey`]s and the [`Context`]
 //! are interoperable.
 //!
+//! All APIs are generic over the context type, so contexts created on caller-provided memory with
+//! [`Secp256k1::preallocated_new`] work with them as well. Without the `std` and `alloc` features,
+//! only the APIs that don't allocate are available, see `no_std_test` for an example.
+//!
 
 // Coding conventions
 #![deny(non_upper_case_globals)]
@@ -47,6 +51,9 @@ extern crate secp256k1;
 pub use secp256k1::hashes;
 #[cfg(any(test, feature = "std"))]
 extern crate core;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+#[macro_use]
+extern crate alloc;
 #[cfg(any(test, feature = "rand"))]
 pub extern crate rand;
 #[cfg(any(test))]
@@ -55,6 +62,8 @@ extern crate rand_core;
 pub extern crate serde;
 #[cfg(all(test, feature = "serde"))]
 extern crate serde_test;
+#[cfg(feature = "std")]
+extern crate std as alloc;
 #[cfg(all(test, feature = "unstable"))]
 extern crate test;
 #[cfg(all(test, target_arch = "wasm32"))]
@@ -88,12 +97,54 @@ pub enum Error {
     CannotProveSurjection,
     /// Given bytes don't represent a valid surjection proof
     InvalidSurjectionProof,
+    /// None of the inputs of the surjection proof's domain matches the codomain
+    SurjectionProofNoMatchingInput,
+    /// Failed to select inputs matching the codomain within the maximum number of iterations
+    SurjectionProofIterationLimit,
+    /// The domain size, number of used inputs or number of iterations are out of bounds
+    InvalidSurjectionProofParameters,
     /// Given bytes don't represent a valid pedersen commitment
     InvalidPedersenCommitment,
     /// Failed to produce a range proof because of an internal error within `libsecp256k1-zkp`
     CannotMakeRangeProof,
     /// Given range proof does not prove that the commitment is within a range
     InvalidRangeProof,
+    /// Range proof exponent must be between -1 and 18
+    InvalidRangeProofExp,
+    /// Range proof minimum number of private bits must be at most 64
+    InvalidRangeProofMinBits,
+    /// Range proof minimum value is larger than the value
+    RangeProofMinValueExceedsValue,
+    /// Range proof value and minimum value would make the proven range exceed 2^64
+    RangeProofValueOutOfBounds,
+    /// Exact value range proofs can't have private bits, a message or a minimum value other than the value
+    InvalidExactValueRangeProof,
+    /// Range proof message is longer than a proof with the given value, exponent and private bits can embed
+    RangeProofMessageTooLong,
+    /// Given bytes don't represent a valid asset id and asset blinding factor
+    InvalidRangeProofMessage,
+    /// The commitments recomputed from the secrets of an unblinded output don't match the output
+    UnblindedCommitmentMismatch,
+    /// Only one of the commitment and the range proof of an issuance's inflation keys is present
+    IssuanceInflationKeysMismatch,
+    /// Given bytes don't represent a valid null, explicit or confidential value
+    InvalidConfidentialValue,
+    /// Given bytes don't represent a valid null, explicit or confidential asset
+    InvalidConfidentialAsset,
+    /// Given bytes don't represent a valid null, explicit or confidential nonce
+    InvalidConfidentialNonce,
+    /// A transaction input has a null asset or value
+    InvalidTransactionInput,
+    /// A transaction output has a null asset or value
+    InvalidTransactionOutput,
+    /// The explicit inputs and issuances of a transaction are larger than its explicit outputs and fees
+    TransactionInputsExceedOutputs,
+    /// The explicit outputs and fees of a transaction are larger than its explicit inputs and issuances
+    TransactionOutputsExceedInputs,
+    /// The commitments of a transaction don't balance
+    UnbalancedTransaction,
+    /// A blinding session has no confidential output to balance the blinding factors
+    NoConfidentialOutput,
     /// Bad generator
     InvalidGenerator,
     /// Tweak must of len 32
@@ -116,6 +167,14 @@ pub enum Error {
     CannotCreateWhitelistSignature,
     /// The given whitelist signature doesn't correctly prove inclusion in the whitelist.
     InvalidWhitelistProof,
+    /// Given bytes don't represent a valid sign-to-contract opening
+    InvalidEcdsaS2cOpening,
+    /// Given signature doesn't commit to the provided data with the given sign-to-contract opening
+    CannotVerifyEcdsaS2cCommitment,
+    /// Given bytes don't represent a valid anti-exfil host commitment or randomness
+    InvalidAntiExfilHostData,
+    /// Given signature is not valid or doesn't commit to the host's randomness
+    CannotVerifyAntiExfilSignature,
 }
 
 // Passthrough Debug to Display, since errors should be user-visible
@@ -124,9 +183,42 @@ impl fmt::Display for Error {
         let str = match *self {
             Error::CannotProveSurjection => "failed to prove surjection",
             Error::InvalidSurjectionProof => "malformed surjection proof",
+            Error::SurjectionProofNoMatchingInput => {
+                "no input of the surjection proof domain matches the codomain"
+            }
+            Error::SurjectionProofIterationLimit => {
+                "surjection proof input selection exhausted the iteration limit"
+            }
+            Error::InvalidSurjectionProofParameters => "invalid surjection proof parameters",
             Error::InvalidPedersenCommitment => "malformed pedersen commitment",
             Error::CannotMakeRangeProof => "failed to generate range proof",
             Error::InvalidRangeProof => "failed to verify range proof",
+            Error::InvalidRangeProofMessage => "malformed range proof asset message",
+            Error::InvalidConfidentialValue => "malformed confidential value",
+            Error::InvalidConfidentialAsset => "malformed confidential asset",
+            Error::InvalidConfidentialNonce => "malformed confidential nonce",
+            Error::InvalidTransactionInput => "transaction input has a null asset or value",
+            Error::InvalidTransactionOutput => "transaction output has a null asset or value",
+            Error::TransactionInputsExceedOutputs => "transaction inputs exceed outputs",
+            Error::TransactionOutputsExceedInputs => "transaction outputs exceed inputs",
+            Error::UnbalancedTransaction => "transaction doesn't balance",
+            Error::NoConfidentialOutput => "no confidential output to balance blinding factors",
+            Error::UnblindedCommitmentMismatch => {
+                "unblinded secrets don't match the output's commitments"
+            }
+            Error::IssuanceInflationKeysMismatch => {
+                "issuance has only one of an inflation keys commitment and range proof"
+            }
+            Error::InvalidRangeProofExp => "range proof exponent must be between -1 and 18",
+            Error::InvalidRangeProofMinBits => "range proof min_bits must be at most 64",
+            Error::RangeProofMinValueExceedsValue => "range proof min_value exceeds the value",
+            Error::RangeProofValueOutOfBounds => "range proof range would exceed 2^64",
+            Error::InvalidExactValueRangeProof => {
+                "exact value range proofs can't have private bits, a message or a different min_value"
+            }
+            Error::RangeProofMessageTooLong => {
+                "range proof message is too long for the proven range"
+            }
             Error::InvalidGenerator => "malformed generator",
             Error::InvalidEcdsaAdaptorSignature => "malformed ecdsa adaptor signature",
             Error::CannotDecryptAdaptorSignature => "failed to decrypt adaptor signature",
@@ -143,6 +235,10 @@ impl fmt::Display for Error {
             Error::InvalidWhitelistProof => {
                 "given whitelist signature doesn't correctly prove inclusion in the whitelist"
             }
+            Error::InvalidEcdsaS2cOpening => "malformed sign-to-contract opening",
+            Error::CannotVerifyEcdsaS2cCommitment => "failed to verify sign-to-contract commitment",
+            Error::InvalidAntiExfilHostData => "malformed anti-exfil host commitment or randomness",
+            Error::CannotVerifyAntiExfilSignature => "failed to verify anti-exfil signature",
         };
 
         f.write_str(str)
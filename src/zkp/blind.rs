@@ -0,0 +1,875 @@
+//! # Confidential transaction outputs
+//!
+//! High-level API to blind the asset and value of Elements-style transaction outputs.
+//! Blinding an output produces a blinded asset [`Generator`], a [`PedersenCommitment`] to the value,
+//! the ephemeral public key that goes into the output's nonce field, a [`RangeProof`] and a [`SurjectionProof`].
+//! Confidential issuances are blinded with an [`IssuanceBlinder`]. A [`BlindingSession`] blinds all
+//! outputs of a transaction at once.
+//!
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+use ZERO_TWEAK;
+use {
+    AssetEntropy, Error, Generator, PedersenCommitment, PublicKey, RangeProof, RangeProofBuilder,
+};
+use {RangeProofMessage, Secp256k1, SecretKey, Signing, SurjectionProof, Tag, Tweak, Verification};
+
+/// The secrets of a confidential transaction output.
+///
+/// Explicit (unblinded) outputs can be represented with zero blinding factors.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct TxOutSecrets {
+    /// The asset id of the output.
+    pub asset: Tag,
+    /// The blinding factor of the output's asset generator.
+    pub asset_blinding_factor: Tweak,
+    /// The value of the output.
+    pub value: u64,
+    /// The blinding factor of the output's value commitment.
+    pub value_blinding_factor: Tweak,
+}
+
+impl TxOutSecrets {
+    /// Constructor.
+    pub fn new(
+        asset: Tag,
+        asset_blinding_factor: Tweak,
+        value: u64,
+        value_blinding_factor: Tweak,
+    ) -> TxOutSecrets {
+        TxOutSecrets {
+            asset,
+            asset_blinding_factor,
+            value,
+            value_blinding_factor,
+        }
+    }
+
+    /// Computes the blinded asset generator of the output.
+    pub fn asset_generator<C: Signing>(&self, secp: &Secp256k1<C>) -> Generator {
+        Generator::new_blinded(secp, self.asset, self.asset_blinding_factor)
+    }
+
+    /// Computes the value commitment of the output.
+    pub fn value_commitment<C: Signing>(&self, secp: &Secp256k1<C>) -> PedersenCommitment {
+        PedersenCommitment::new(
+            secp,
+            self.value,
+            self.value_blinding_factor,
+            self.asset_generator(secp),
+        )
+    }
+}
+
+/// The public data of a blinded transaction output.
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub struct BlindedTxOut {
+    /// The blinded asset generator.
+    pub asset: Generator,
+    /// The commitment to the value.
+    pub value: PedersenCommitment,
+    /// The ephemeral public key the receiver needs to rewind the range proof.
+    pub nonce: PublicKey,
+    /// The proof that the value is within range, which also carries the asset id and asset blinding factor.
+    pub range_proof: RangeProof,
+    /// The proof that the asset is one of the assets of the inputs.
+    pub surjection_proof: SurjectionProof,
+}
+
+/// Unblinds a confidential output using the receiver's blinding key.
+///
+/// Rewinds the range proof with the ECDH nonce derived from `blinding_key` and the output's `nonce`,
+/// then recomputes the asset generator and the value commitment from the recovered secrets. Fails
+/// if the range proof is invalid for `script_pubkey` or if the recomputed commitments don't match
+/// `asset` and `value`.
+pub fn unblind_output<C: Signing + Verification>(
+    secp: &Secp256k1<C>,
+    blinding_key: SecretKey,
+    asset: Generator,
+    value: PedersenCommitment,
+    nonce: PublicKey,
+    range_proof: &RangeProof,
+    script_pubkey: &[u8],
+) -> Result<TxOutSecrets, Error> {
+    let (opening, _) = range_proof.rewind_with_blinding_key(
+        secp,
+        value,
+        blinding_key,
+        nonce,
+        script_pubkey,
+        asset,
+    )?;
+    let message = opening.asset_message()?;
+
+    let secrets = TxOutSecrets::new(
+        message.asset,
+        message.asset_blinding_factor,
+        opening.value,
+        opening.blinding_factor,
+    );
+
+    if secrets.asset_generator(secp) != asset || secrets.value_commitment(secp) != value {
+        return Err(Error::UnblindedCommitmentMismatch);
+    }
+
+    Ok(secrets)
+}
+
+/// The public data of a blinded issuance.
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub struct BlindedIssuance {
+    /// The commitment to the issued amount of the asset.
+    pub amount: PedersenCommitment,
+    /// The proof that the issued amount is within range.
+    pub amount_range_proof: RangeProof,
+    /// The commitment to the issued amount of reissuance tokens, if any.
+    pub inflation_keys: Option<PedersenCommitment>,
+    /// The proof that the issued amount of reissuance tokens is within range, if any.
+    pub inflation_keys_range_proof: Option<RangeProof>,
+}
+
+/// The secrets of a blinded issuance.
+///
+/// Issued assets are explicit, so the asset blinding factors are zero. The secrets can be used
+/// like the secrets of an input when blinding the outputs of the issuing transaction.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct IssuanceSecrets {
+    /// The secrets of the issued amount of the asset.
+    pub amount: TxOutSecrets,
+    /// The secrets of the issued amount of reissuance tokens, if any.
+    pub inflation_keys: Option<TxOutSecrets>,
+}
+
+/// Blinds and unblinds the amounts of an issuance.
+///
+/// The issued amount is committed to under the generator of the asset and the amount of reissuance
+/// tokens under the generator of the token. Like Elements, both range proofs use the issuance
+/// blinding key directly as nonce and don't commit to a script, so the issuer's wallet can rewind
+/// them with the same key.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub struct IssuanceBlinder {
+    asset: Tag,
+    token: Tag,
+    blinding_key: SecretKey,
+}
+
+impl IssuanceBlinder {
+    /// Creates a blinder for the issuance or reissuance with the given `entropy`.
+    pub fn new(entropy: AssetEntropy, blinding_key: SecretKey) -> IssuanceBlinder {
+        IssuanceBlinder {
+            asset: Tag::from_issuance_entropy(entropy),
+            token: Tag::reissuance_token(entropy, true),
+            blinding_key,
+        }
+    }
+
+    /// The asset id of the issuance.
+    pub fn asset(&self) -> Tag {
+        self.asset
+    }
+
+    /// The id of the reissuance token of a blinded issuance.
+    pub fn token(&self) -> Tag {
+        self.token
+    }
+
+    /// Blinds an issuance of `amount` of the asset and, unless it is a reissuance, of an
+    /// `inflation_keys` amount of reissuance tokens together with its value blinding factor.
+    pub fn blind<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        amount: u64,
+        amount_blinding_factor: Tweak,
+        inflation_keys: Option<(u64, Tweak)>,
+    ) -> Result<(BlindedIssuance, IssuanceSecrets), Error> {
+        let amount_secrets =
+            TxOutSecrets::new(self.asset, ZERO_TWEAK, amount, amount_blinding_factor);
+        let (amount, amount_range_proof) = self.blind_amount(secp, &amount_secrets)?;
+
+        let (inflation_keys, inflation_keys_range_proof, inflation_keys_secrets) =
+            match inflation_keys {
+                Some((value, value_blinding_factor)) => {
+                    let secrets =
+                        TxOutSecrets::new(self.token, ZERO_TWEAK, value, value_blinding_factor);
+                    let (commitment, range_proof) = self.blind_amount(secp, &secrets)?;
+                    (Some(commitment), Some(range_proof), Some(secrets))
+                }
+                None => (None, None, None),
+            };
+
+        let issuance = BlindedIssuance {
+            amount,
+            amount_range_proof,
+            inflation_keys,
+            inflation_keys_range_proof,
+        };
+        let secrets = IssuanceSecrets {
+            amount: amount_secrets,
+            inflation_keys: inflation_keys_secrets,
+        };
+
+        Ok((issuance, secrets))
+    }
+
+    /// Unblinds an issuance by rewinding its range proofs.
+    ///
+    /// Fails if a range proof is invalid, if only one of the commitment and the range proof of the
+    /// reissuance tokens is present or if the recovered secrets don't match the commitments.
+    pub fn unblind<C: Signing + Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        issuance: &BlindedIssuance,
+    ) -> Result<IssuanceSecrets, Error> {
+        let amount = self.unblind_amount(
+            secp,
+            self.asset,
+            issuance.amount,
+            &issuance.amount_range_proof,
+        )?;
+
+        let inflation_keys = match (
+            issuance.inflation_keys,
+            issuance.inflation_keys_range_proof.as_ref(),
+        ) {
+            (Some(commitment), Some(range_proof)) => {
+                Some(self.unblind_amount(secp, self.token, commitment, range_proof)?)
+            }
+            (None, None) => None,
+            _ => return Err(Error::IssuanceInflationKeysMismatch),
+        };
+
+        Ok(IssuanceSecrets {
+            amount,
+            inflation_keys,
+        })
+    }
+
+    fn blind_amount<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        secrets: &TxOutSecrets,
+    ) -> Result<(PedersenCommitment, RangeProof), Error> {
+        let generator = secrets.asset_generator(secp);
+        let commitment = secrets.value_commitment(secp);
+        let message =
+            RangeProofMessage::new(secrets.asset, secrets.asset_blinding_factor).serialize();
+
+        let range_proof = RangeProofBuilder::confidential(
+            secrets.value,
+            commitment,
+            secrets.value_blinding_factor,
+            generator,
+            self.blinding_key,
+        )
+        .message(&message)
+        .build(secp)?;
+
+        Ok((commitment, range_proof))
+    }
+
+    fn unblind_amount<C: Signing + Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        asset: Tag,
+        commitment: PedersenCommitment,
+        range_proof: &RangeProof,
+    ) -> Result<TxOutSecrets, Error> {
+        let generator = Generator::new_unblinded(secp, asset);
+        let (opening, _) =
+            range_proof.rewind(secp, commitment, self.blinding_key, &[], generator)?;
+        let message = opening.asset_message()?;
+
+        let secrets = TxOutSecrets::new(
+            message.asset,
+            message.asset_blinding_factor,
+            opening.value,
+            opening.blinding_factor,
+        );
+
+        if secrets.asset_generator(secp) != generator
+            || secrets.value_commitment(secp) != commitment
+        {
+            return Err(Error::UnblindedCommitmentMismatch);
+        }
+
+        Ok(secrets)
+    }
+}
+
+#[cfg(feature = "rand")]
+mod with_rand {
+    use super::*;
+    use {compute_adaptive_blinding_factor, CommitmentSecrets, Error, SecretKey};
+
+    /// The minimum number of private bits of the value in an output's range proof.
+    const RANGEPROOF_MIN_BITS: u8 = 52;
+
+    /// Blinds an output sending `value` of `asset` to the owner of `receiver_blinding_pk`.
+    ///
+    /// The asset and value blinding factors are chosen at random. The range proof commits to
+    /// `script_pubkey` as Elements does. `inputs` are the secrets of all inputs of the transaction
+    /// and form the domain of the surjection proof.
+    ///
+    /// Use [`blind_last_output`] for the last confidential output of a transaction, so that the
+    /// value commitments of the transaction balance.
+    pub fn blind_output<C: Signing, R: Rng>(
+        secp: &Secp256k1<C>,
+        rng: &mut R,
+        asset: Tag,
+        value: u64,
+        receiver_blinding_pk: PublicKey,
+        script_pubkey: &[u8],
+        inputs: &[TxOutSecrets],
+    ) -> Result<(BlindedTxOut, TxOutSecrets), Error> {
+        let secrets = TxOutSecrets::new(asset, Tweak::new(rng), value, Tweak::new(rng));
+
+        blind_with_secrets(
+            secp,
+            rng,
+            secrets,
+            receiver_blinding_pk,
+            script_pubkey,
+            inputs,
+        )
+    }
+
+    /// Blinds the last confidential output of a transaction.
+    ///
+    /// Same as [`blind_output`], but the value blinding factor is chosen such that the commitments of
+    /// `inputs` sum up to the commitments of `outputs` plus the commitment of this output. `outputs`
+    /// are the secrets of all other outputs of the transaction, including explicit ones.
+    #[allow(clippy::too_many_arguments)]
+    pub fn blind_last_output<C: Signing, R: Rng>(
+        secp: &Secp256k1<C>,
+        rng: &mut R,
+        asset: Tag,
+        value: u64,
+        receiver_blinding_pk: PublicKey,
+        script_pubkey: &[u8],
+        inputs: &[TxOutSecrets],
+        outputs: &[TxOutSecrets],
+    ) -> Result<(BlindedTxOut, TxOutSecrets), Error> {
+        let asset_blinding_factor = Tweak::new(rng);
+        let value_blinding_factor = compute_adaptive_blinding_factor(
+            secp,
+            value,
+            asset_blinding_factor,
+            &commitment_secrets(inputs),
+            &commitment_secrets(outputs),
+        );
+        let secrets = TxOutSecrets::new(asset, asset_blinding_factor, value, value_blinding_factor);
+
+        blind_with_secrets(
+            secp,
+            rng,
+            secrets,
+            receiver_blinding_pk,
+            script_pubkey,
+            inputs,
+        )
+    }
+
+    /// Collects the inputs and outputs of a transaction to blind all of its outputs at once.
+    ///
+    /// Every confidential output gets random blinding factors, except for the value blinding factor of
+    /// the last confidential output, which is solved for such that the transaction balances. This is
+    /// what Elements Core does when blinding a transaction.
+    #[derive(Debug, Clone, Default)]
+    pub struct BlindingSession<'a> {
+        inputs: Vec<TxOutSecrets>,
+        outputs: Vec<OutputRequest<'a>>,
+    }
+
+    #[derive(Debug, Clone)]
+    enum OutputRequest<'a> {
+        Explicit(TxOutSecrets),
+        Confidential {
+            asset: Tag,
+            value: u64,
+            receiver_blinding_pk: PublicKey,
+            script_pubkey: &'a [u8],
+        },
+    }
+
+    impl<'a> BlindingSession<'a> {
+        /// Creates an empty session.
+        pub fn new() -> BlindingSession<'a> {
+            BlindingSession::default()
+        }
+
+        /// Adds the secrets of an input. Explicit inputs have zero blinding factors.
+        pub fn add_input(&mut self, secrets: TxOutSecrets) {
+            self.inputs.push(secrets);
+        }
+
+        /// Adds the secrets of an issuance, which count as inputs.
+        pub fn add_issuance(&mut self, secrets: IssuanceSecrets) {
+            self.inputs.push(secrets.amount);
+            self.inputs.extend(secrets.inflation_keys);
+        }
+
+        /// Adds an explicit output, such as the fee output.
+        pub fn add_explicit_output(&mut self, asset: Tag, value: u64) {
+            self.outputs.push(OutputRequest::Explicit(TxOutSecrets::new(
+                asset, ZERO_TWEAK, value, ZERO_TWEAK,
+            )));
+        }
+
+        /// Adds a confidential output sending `value` of `asset` to the owner of `receiver_blinding_pk`.
+        pub fn add_output(
+            &mut self,
+            asset: Tag,
+            value: u64,
+            receiver_blinding_pk: PublicKey,
+            script_pubkey: &'a [u8],
+        ) {
+            self.outputs.push(OutputRequest::Confidential {
+                asset,
+                value,
+                receiver_blinding_pk,
+                script_pubkey,
+            });
+        }
+
+        /// Blinds all confidential outputs.
+        ///
+        /// Returns the blinded outputs and their secrets in the order the confidential outputs were
+        /// added. Fails with [`Error::NoConfidentialOutput`] if no confidential output was added.
+        pub fn blind<C: Signing, R: Rng>(
+            &self,
+            secp: &Secp256k1<C>,
+            rng: &mut R,
+        ) -> Result<Vec<(BlindedTxOut, TxOutSecrets)>, Error> {
+            let last = self
+                .outputs
+                .iter()
+                .rposition(|o| match *o {
+                    OutputRequest::Confidential { .. } => true,
+                    OutputRequest::Explicit(_) => false,
+                })
+                .ok_or(Error::NoConfidentialOutput)?;
+
+            let mut other_outputs = Vec::with_capacity(self.outputs.len());
+            let mut blinded = Vec::with_capacity(self.outputs.len());
+            for (i, output) in self.outputs.iter().enumerate() {
+                match *output {
+                    OutputRequest::Explicit(secrets) => other_outputs.push(secrets),
+                    OutputRequest::Confidential { .. } if i == last => {}
+                    OutputRequest::Confidential {
+                        asset,
+                        value,
+                        receiver_blinding_pk,
+                        script_pubkey,
+                    } => {
+                        let (txout, secrets) = blind_output(
+                            secp,
+                            rng,
+                            asset,
+                            value,
+                            receiver_blinding_pk,
+                            script_pubkey,
+                            &self.inputs,
+                        )?;
+                        other_outputs.push(secrets);
+                        blinded.push((txout, secrets));
+                    }
+                }
+            }
+
+            if let OutputRequest::Confidential {
+                asset,
+                value,
+                receiver_blinding_pk,
+                script_pubkey,
+            } = self.outputs[last]
+            {
+                blinded.push(blind_last_output(
+                    secp,
+                    rng,
+                    asset,
+                    value,
+                    receiver_blinding_pk,
+                    script_pubkey,
+                    &self.inputs,
+                    &other_outputs,
+                )?);
+            }
+
+            Ok(blinded)
+        }
+    }
+
+    fn blind_with_secrets<C: Signing, R: Rng>(
+        secp: &Secp256k1<C>,
+        rng: &mut R,
+        secrets: TxOutSecrets,
+        receiver_blinding_pk: PublicKey,
+        script_pubkey: &[u8],
+        inputs: &[TxOutSecrets],
+    ) -> Result<(BlindedTxOut, TxOutSecrets), Error> {
+        let asset = secrets.asset_generator(secp);
+        let value = secrets.value_commitment(secp);
+
+        let ephemeral_sk = SecretKey::new(rng);
+        let nonce = PublicKey::from_secret_key(secp, &ephemeral_sk);
+
+        let message = RangeProofMessage::new(secrets.asset, secrets.asset_blinding_factor);
+        let range_proof = RangeProof::new_for_output(
+            secp,
+            0,
+            value,
+            secrets.value,
+            secrets.value_blinding_factor,
+            &message.serialize(),
+            script_pubkey,
+            ephemeral_sk,
+            receiver_blinding_pk,
+            0,
+            RANGEPROOF_MIN_BITS,
+            asset,
+        )?;
+
+        let domain = inputs
+            .iter()
+            .map(|i| (i.asset_generator(secp), i.asset, i.asset_blinding_factor))
+            .collect::<Vec<_>>();
+        let surjection_proof = SurjectionProof::new(
+            secp,
+            rng,
+            secrets.asset,
+            secrets.asset_blinding_factor,
+            &domain,
+        )?;
+
+        let txout = BlindedTxOut {
+            asset,
+            value,
+            nonce,
+            range_proof,
+            surjection_proof,
+        };
+
+        Ok((txout, secrets))
+    }
+
+    fn commitment_secrets(secrets: &[TxOutSecrets]) -> Vec<CommitmentSecrets> {
+        secrets
+            .iter()
+            .map(|s| {
+                CommitmentSecrets::new(s.value, s.value_blinding_factor, s.asset_blinding_factor)
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "rand")]
+pub use self::with_rand::{blind_last_output, blind_output, BlindingSession};
+
+#[cfg(all(test, feature = "global-context"))]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use {verify_commitments_sum_to_equal, RangeProofMessage, SECP256K1, ZERO_TWEAK};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn test_blind_outputs() {
+        let asset = Tag::random();
+        let inputs = [
+            TxOutSecrets::new(
+                asset,
+                Tweak::new(&mut thread_rng()),
+                10_000,
+                Tweak::new(&mut thread_rng()),
+            ),
+            TxOutSecrets::new(
+                asset,
+                Tweak::new(&mut thread_rng()),
+                5_000,
+                Tweak::new(&mut thread_rng()),
+            ),
+        ];
+        let (_, receiver_pk) = SECP256K1.generate_keypair(&mut thread_rng());
+        let (_, change_pk) = SECP256K1.generate_keypair(&mut thread_rng());
+        let fee = TxOutSecrets::new(asset, ZERO_TWEAK, 500, ZERO_TWEAK);
+
+        let (output, output_secrets) = blind_output(
+            SECP256K1,
+            &mut thread_rng(),
+            asset,
+            12_000,
+            receiver_pk,
+            b"receiver",
+            &inputs,
+        )
+        .unwrap();
+        let (change, change_secrets) = blind_last_output(
+            SECP256K1,
+            &mut thread_rng(),
+            asset,
+            2_500,
+            change_pk,
+            b"change",
+            &inputs,
+            &[output_secrets, fee],
+        )
+        .unwrap();
+
+        let input_assets = inputs
+            .iter()
+            .map(|i| i.asset_generator(SECP256K1))
+            .collect::<Vec<_>>();
+        for (txout, secrets, script_pubkey) in &[
+            (&output, output_secrets, &b"receiver"[..]),
+            (&change, change_secrets, &b"change"[..]),
+        ] {
+            assert_eq!(txout.asset, secrets.asset_generator(SECP256K1));
+            assert_eq!(txout.value, secrets.value_commitment(SECP256K1));
+            txout
+                .range_proof
+                .verify(SECP256K1, txout.value, script_pubkey, txout.asset)
+                .unwrap();
+            assert!(txout
+                .surjection_proof
+                .verify(SECP256K1, txout.asset, &input_assets));
+        }
+
+        assert!(verify_commitments_sum_to_equal(
+            SECP256K1,
+            &[
+                inputs[0].value_commitment(SECP256K1),
+                inputs[1].value_commitment(SECP256K1)
+            ],
+            &[output.value, change.value, fee.value_commitment(SECP256K1)],
+        ));
+    }
+
+    #[test]
+    fn test_unblind_output() {
+        let asset = Tag::random();
+        let inputs = [TxOutSecrets::new(
+            asset,
+            Tweak::new(&mut thread_rng()),
+            10_000,
+            Tweak::new(&mut thread_rng()),
+        )];
+        let (blinding_key, blinding_pk) = SECP256K1.generate_keypair(&mut thread_rng());
+
+        let (txout, secrets) = blind_output(
+            SECP256K1,
+            &mut thread_rng(),
+            asset,
+            10_000,
+            blinding_pk,
+            b"script",
+            &inputs,
+        )
+        .unwrap();
+
+        let unblinded = unblind_output(
+            SECP256K1,
+            blinding_key,
+            txout.asset,
+            txout.value,
+            txout.nonce,
+            &txout.range_proof,
+            b"script",
+        )
+        .unwrap();
+        assert_eq!(unblinded, secrets);
+
+        // wrong blinding key
+        let (other_key, _) = SECP256K1.generate_keypair(&mut thread_rng());
+        assert!(unblind_output(
+            SECP256K1,
+            other_key,
+            txout.asset,
+            txout.value,
+            txout.nonce,
+            &txout.range_proof,
+            b"script",
+        )
+        .is_err());
+
+        // wrong script pubkey
+        assert_eq!(
+            unblind_output(
+                SECP256K1,
+                blinding_key,
+                txout.asset,
+                txout.value,
+                txout.nonce,
+                &txout.range_proof,
+                b"other script",
+            ),
+            Err(Error::InvalidRangeProof)
+        );
+
+        // a range proof whose message doesn't match the asset generator
+        let other_asset =
+            TxOutSecrets::new(Tag::random(), Tweak::new(&mut thread_rng()), 0, ZERO_TWEAK);
+        let ephemeral_key = SecretKey::new(&mut thread_rng());
+        let range_proof = RangeProof::new_for_output(
+            SECP256K1,
+            0,
+            txout.value,
+            secrets.value,
+            secrets.value_blinding_factor,
+            &RangeProofMessage::new(other_asset.asset, other_asset.asset_blinding_factor)
+                .serialize(),
+            b"script",
+            ephemeral_key,
+            blinding_pk,
+            0,
+            52,
+            txout.asset,
+        )
+        .unwrap();
+        assert_eq!(
+            unblind_output(
+                SECP256K1,
+                blinding_key,
+                txout.asset,
+                txout.value,
+                PublicKey::from_secret_key(SECP256K1, &ephemeral_key),
+                &range_proof,
+                b"script",
+            ),
+            Err(Error::UnblindedCommitmentMismatch)
+        );
+    }
+
+    #[test]
+    fn test_blind_issuance() {
+        let entropy = AssetEntropy::from_inner(Tag::random().into());
+        let blinding_key = SecretKey::new(&mut thread_rng());
+        let blinder = IssuanceBlinder::new(entropy, blinding_key);
+        assert_eq!(blinder.asset(), Tag::from_issuance_entropy(entropy));
+        assert_eq!(blinder.token(), Tag::reissuance_token(entropy, true));
+
+        let (issuance, secrets) = blinder
+            .blind(
+                SECP256K1,
+                21_000_000,
+                Tweak::new(&mut thread_rng()),
+                Some((1, Tweak::new(&mut thread_rng()))),
+            )
+            .unwrap();
+
+        let asset = Generator::new_unblinded(SECP256K1, blinder.asset());
+        let token = Generator::new_unblinded(SECP256K1, blinder.token());
+        issuance
+            .amount_range_proof
+            .verify(SECP256K1, issuance.amount, &[], asset)
+            .unwrap();
+        issuance
+            .inflation_keys_range_proof
+            .as_ref()
+            .unwrap()
+            .verify(SECP256K1, issuance.inflation_keys.unwrap(), &[], token)
+            .unwrap();
+        assert_eq!(issuance.amount, secrets.amount.value_commitment(SECP256K1));
+        assert_eq!(
+            issuance.inflation_keys,
+            secrets
+                .inflation_keys
+                .map(|s| s.value_commitment(SECP256K1))
+        );
+
+        assert_eq!(blinder.unblind(SECP256K1, &issuance).unwrap(), secrets);
+
+        // a reissuance doesn't issue tokens
+        let (reissuance, reissuance_secrets) = blinder
+            .blind(SECP256K1, 5_000, Tweak::new(&mut thread_rng()), None)
+            .unwrap();
+        assert_eq!(reissuance.inflation_keys, None);
+        assert_eq!(
+            blinder.unblind(SECP256K1, &reissuance).unwrap(),
+            reissuance_secrets
+        );
+
+        // only one of the inflation keys commitment and range proof
+        let mut missing_proof = issuance.clone();
+        missing_proof.inflation_keys_range_proof = None;
+        assert_eq!(
+            blinder.unblind(SECP256K1, &missing_proof),
+            Err(Error::IssuanceInflationKeysMismatch)
+        );
+        let mut missing_commitment = issuance.clone();
+        missing_commitment.inflation_keys = None;
+        assert_eq!(
+            blinder.unblind(SECP256K1, &missing_commitment),
+            Err(Error::IssuanceInflationKeysMismatch)
+        );
+
+        // wrong blinding key
+        let other = IssuanceBlinder::new(entropy, SecretKey::new(&mut thread_rng()));
+        assert!(other.unblind(SECP256K1, &issuance).is_err());
+
+        // wrong entropy
+        let other =
+            IssuanceBlinder::new(AssetEntropy::from_inner(Tag::random().into()), blinding_key);
+        assert!(other.unblind(SECP256K1, &issuance).is_err());
+    }
+
+    #[test]
+    fn test_blinding_session() {
+        let asset = Tag::random();
+        let (_, receiver_pk) = SECP256K1.generate_keypair(&mut thread_rng());
+        let (_, change_pk) = SECP256K1.generate_keypair(&mut thread_rng());
+
+        let inputs = [
+            TxOutSecrets::new(
+                asset,
+                Tweak::new(&mut thread_rng()),
+                10_000,
+                Tweak::new(&mut thread_rng()),
+            ),
+            TxOutSecrets::new(asset, ZERO_TWEAK, 5_000, ZERO_TWEAK),
+        ];
+
+        let mut session = BlindingSession::new();
+        session.add_input(inputs[0]);
+        session.add_input(inputs[1]);
+        assert_eq!(
+            session.blind(SECP256K1, &mut thread_rng()),
+            Err(Error::NoConfidentialOutput)
+        );
+
+        session.add_output(asset, 12_000, receiver_pk, b"receiver");
+        session.add_output(asset, 2_500, change_pk, b"change");
+        session.add_explicit_output(asset, 500);
+        let outputs = session.blind(SECP256K1, &mut thread_rng()).unwrap();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].1.value, 12_000);
+        assert_eq!(outputs[1].1.value, 2_500);
+
+        let input_assets = inputs
+            .iter()
+            .map(|i| i.asset_generator(SECP256K1))
+            .collect::<Vec<_>>();
+        for (txout, secrets) in &outputs {
+            assert_eq!(txout.value, secrets.value_commitment(SECP256K1));
+            assert!(txout
+                .surjection_proof
+                .verify(SECP256K1, txout.asset, &input_assets));
+        }
+
+        let fee = TxOutSecrets::new(asset, ZERO_TWEAK, 500, ZERO_TWEAK);
+        assert!(verify_commitments_sum_to_equal(
+            SECP256K1,
+            &inputs
+                .iter()
+                .map(|i| i.value_commitment(SECP256K1))
+                .collect::<Vec<_>>(),
+            &[
+                outputs[0].0.value,
+                outputs[1].0.value,
+                fee.value_commitment(SECP256K1)
+            ],
+        ));
+    }
+}
@@ -0,0 +1,732 @@
+//! # Confidential values, assets and nonces
+//!
+//! Elements transaction outputs carry their value, asset and nonce either explicitly, as a
+//! commitment, or not at all (null). This module implements the consensus encoding of these three
+//! fields:
+//!
+//! | Field   | Null   | Explicit                | Confidential                        |
+//! |---------|--------|-------------------------|-------------------------------------|
+//! | value   | `0x00` | `0x01` + 8-byte BE u64  | [`PedersenCommitment`] (`0x08/0x09`)|
+//! | asset   | `0x00` | `0x01` + 32-byte tag    | [`Generator`] (`0x0a/0x0b`)         |
+//! | nonce   | `0x00` | `0x01` + 32 bytes       | [`PublicKey`] (`0x02/0x03`)         |
+//!
+
+use core::{fmt, str};
+use std::collections::BTreeMap;
+use std::io;
+use {from_hex, verify_commitments_sum_to_equal, Error, Generator, PedersenCommitment, PublicKey};
+use {Secp256k1, Signing, Tag};
+
+/// The value of an Elements transaction output.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum ConfidentialValue {
+    /// No value.
+    Null,
+    /// An explicit value.
+    Explicit(u64),
+    /// A commitment to a value.
+    Confidential(PedersenCommitment),
+}
+
+/// The asset of an Elements transaction output.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum ConfidentialAsset {
+    /// No asset.
+    Null,
+    /// An explicit asset.
+    Explicit(Tag),
+    /// A blinded asset.
+    Confidential(Generator),
+}
+
+/// The nonce of an Elements transaction output.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum ConfidentialNonce {
+    /// No nonce.
+    Null,
+    /// An explicit nonce.
+    Explicit([u8; 32]),
+    /// A public key, usually the ephemeral key used to derive the range proof nonce.
+    Confidential(PublicKey),
+}
+
+/// Reads the prefix byte of a field and, unless it is null, the `explicit_len` or 32 bytes following it.
+///
+/// Returns the prefix and fills `rest` with the bytes after it.
+fn read_field<R: io::Read>(
+    mut r: R,
+    explicit_len: usize,
+    confidential_prefixes: [u8; 2],
+    rest: &mut [u8; 32],
+) -> Result<Option<u8>, ()> {
+    let mut prefix = [0u8; 1];
+    r.read_exact(&mut prefix).map_err(|_| ())?;
+
+    let len = match prefix[0] {
+        0 => return Ok(None),
+        1 => explicit_len,
+        p if p == confidential_prefixes[0] || p == confidential_prefixes[1] => 32,
+        _ => return Err(()),
+    };
+    r.read_exact(&mut rest[..len]).map_err(|_| ())?;
+
+    Ok(Some(prefix[0]))
+}
+
+fn confidential_bytes(prefix: u8, rest: &[u8; 32]) -> [u8; 33] {
+    let mut bytes = [0u8; 33];
+    bytes[0] = prefix;
+    bytes[1..].copy_from_slice(rest);
+    bytes
+}
+
+impl ConfidentialValue {
+    /// Returns the length of the consensus encoding.
+    pub fn encoded_length(&self) -> usize {
+        match *self {
+            ConfidentialValue::Null => 1,
+            ConfidentialValue::Explicit(_) => 9,
+            ConfidentialValue::Confidential(_) => 33,
+        }
+    }
+
+    /// Serializes the value using the consensus encoding.
+    pub fn serialize(&self) -> Vec<u8> {
+        match *self {
+            ConfidentialValue::Null => vec![0],
+            ConfidentialValue::Explicit(value) => {
+                let mut bytes = vec![1; 9];
+                for (i, byte) in bytes[1..].iter_mut().enumerate() {
+                    *byte = (value >> (56 - 8 * i)) as u8;
+                }
+                bytes
+            }
+            ConfidentialValue::Confidential(commitment) => commitment.serialize().to_vec(),
+        }
+    }
+
+    /// Writes the consensus encoding of the value to `w`, returning the number of bytes written.
+    pub fn consensus_encode<W: io::Write>(&self, mut w: W) -> io::Result<usize> {
+        w.write_all(&self.serialize())?;
+        Ok(self.encoded_length())
+    }
+
+    /// Reads a consensus encoded value from `r`.
+    pub fn consensus_decode<R: io::Read>(r: R) -> Result<ConfidentialValue, Error> {
+        let mut rest = [0u8; 32];
+
+        match read_field(r, 8, [0x08, 0x09], &mut rest) {
+            Ok(None) => Ok(ConfidentialValue::Null),
+            Ok(Some(1)) => {
+                let value = rest[..8]
+                    .iter()
+                    .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte));
+                Ok(ConfidentialValue::Explicit(value))
+            }
+            Ok(Some(prefix)) => {
+                let bytes = confidential_bytes(prefix, &rest);
+                PedersenCommitment::from_slice(&bytes)
+                    .map(ConfidentialValue::Confidential)
+                    .map_err(|_| Error::InvalidConfidentialValue)
+            }
+            Err(()) => Err(Error::InvalidConfidentialValue),
+        }
+    }
+
+    /// Parses a consensus encoded value, which must span the whole slice.
+    pub fn from_slice(mut bytes: &[u8]) -> Result<ConfidentialValue, Error> {
+        let value = ConfidentialValue::consensus_decode(&mut bytes)?;
+
+        if !bytes.is_empty() {
+            return Err(Error::InvalidConfidentialValue);
+        }
+
+        Ok(value)
+    }
+
+    /// Returns whether the value is null.
+    pub fn is_null(&self) -> bool {
+        *self == ConfidentialValue::Null
+    }
+
+    /// Returns the explicit value, if any.
+    pub fn explicit(&self) -> Option<u64> {
+        match *self {
+            ConfidentialValue::Explicit(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value commitment, if any.
+    pub fn commitment(&self) -> Option<PedersenCommitment> {
+        match *self {
+            ConfidentialValue::Confidential(commitment) => Some(commitment),
+            _ => None,
+        }
+    }
+}
+
+impl From<PedersenCommitment> for ConfidentialValue {
+    fn from(commitment: PedersenCommitment) -> Self {
+        ConfidentialValue::Confidential(commitment)
+    }
+}
+
+impl ConfidentialAsset {
+    /// Returns the length of the consensus encoding.
+    pub fn encoded_length(&self) -> usize {
+        match *self {
+            ConfidentialAsset::Null => 1,
+            ConfidentialAsset::Explicit(_) | ConfidentialAsset::Confidential(_) => 33,
+        }
+    }
+
+    /// Serializes the asset using the consensus encoding.
+    pub fn serialize(&self) -> Vec<u8> {
+        match *self {
+            ConfidentialAsset::Null => vec![0],
+            ConfidentialAsset::Explicit(tag) => {
+                let mut bytes = vec![1; 33];
+                bytes[1..].copy_from_slice(tag.as_ref());
+                bytes
+            }
+            ConfidentialAsset::Confidential(generator) => generator.serialize().to_vec(),
+        }
+    }
+
+    /// Writes the consensus encoding of the asset to `w`, returning the number of bytes written.
+    pub fn consensus_encode<W: io::Write>(&self, mut w: W) -> io::Result<usize> {
+        w.write_all(&self.serialize())?;
+        Ok(self.encoded_length())
+    }
+
+    /// Reads a consensus encoded asset from `r`.
+    pub fn consensus_decode<R: io::Read>(r: R) -> Result<ConfidentialAsset, Error> {
+        let mut rest = [0u8; 32];
+
+        match read_field(r, 32, [0x0a, 0x0b], &mut rest) {
+            Ok(None) => Ok(ConfidentialAsset::Null),
+            Ok(Some(1)) => Ok(ConfidentialAsset::Explicit(Tag::from(rest))),
+            Ok(Some(prefix)) => {
+                let bytes = confidential_bytes(prefix, &rest);
+                Generator::from_slice(&bytes)
+                    .map(ConfidentialAsset::Confidential)
+                    .map_err(|_| Error::InvalidConfidentialAsset)
+            }
+            Err(()) => Err(Error::InvalidConfidentialAsset),
+        }
+    }
+
+    /// Parses a consensus encoded asset, which must span the whole slice.
+    pub fn from_slice(mut bytes: &[u8]) -> Result<ConfidentialAsset, Error> {
+        let asset = ConfidentialAsset::consensus_decode(&mut bytes)?;
+
+        if !bytes.is_empty() {
+            return Err(Error::InvalidConfidentialAsset);
+        }
+
+        Ok(asset)
+    }
+
+    /// Returns whether the asset is null.
+    pub fn is_null(&self) -> bool {
+        *self == ConfidentialAsset::Null
+    }
+
+    /// Returns the explicit asset, if any.
+    pub fn explicit(&self) -> Option<Tag> {
+        match *self {
+            ConfidentialAsset::Explicit(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// Returns the blinded asset generator, if any.
+    pub fn commitment(&self) -> Option<Generator> {
+        match *self {
+            ConfidentialAsset::Confidential(generator) => Some(generator),
+            _ => None,
+        }
+    }
+}
+
+impl From<Generator> for ConfidentialAsset {
+    fn from(generator: Generator) -> Self {
+        ConfidentialAsset::Confidential(generator)
+    }
+}
+
+impl From<Tag> for ConfidentialAsset {
+    fn from(tag: Tag) -> Self {
+        ConfidentialAsset::Explicit(tag)
+    }
+}
+
+impl ConfidentialNonce {
+    /// Returns the length of the consensus encoding.
+    pub fn encoded_length(&self) -> usize {
+        match *self {
+            ConfidentialNonce::Null => 1,
+            ConfidentialNonce::Explicit(_) | ConfidentialNonce::Confidential(_) => 33,
+        }
+    }
+
+    /// Serializes the nonce using the consensus encoding.
+    pub fn serialize(&self) -> Vec<u8> {
+        match *self {
+            ConfidentialNonce::Null => vec![0],
+            ConfidentialNonce::Explicit(nonce) => {
+                let mut bytes = vec![1; 33];
+                bytes[1..].copy_from_slice(&nonce);
+                bytes
+            }
+            ConfidentialNonce::Confidential(pk) => pk.serialize().to_vec(),
+        }
+    }
+
+    /// Writes the consensus encoding of the nonce to `w`, returning the number of bytes written.
+    pub fn consensus_encode<W: io::Write>(&self, mut w: W) -> io::Result<usize> {
+        w.write_all(&self.serialize())?;
+        Ok(self.encoded_length())
+    }
+
+    /// Reads a consensus encoded nonce from `r`.
+    pub fn consensus_decode<R: io::Read>(r: R) -> Result<ConfidentialNonce, Error> {
+        let mut rest = [0u8; 32];
+
+        match read_field(r, 32, [0x02, 0x03], &mut rest) {
+            Ok(None) => Ok(ConfidentialNonce::Null),
+            Ok(Some(1)) => Ok(ConfidentialNonce::Explicit(rest)),
+            Ok(Some(prefix)) => {
+                let bytes = confidential_bytes(prefix, &rest);
+                PublicKey::from_slice(&bytes)
+                    .map(ConfidentialNonce::Confidential)
+                    .map_err(|_| Error::InvalidConfidentialNonce)
+            }
+            Err(()) => Err(Error::InvalidConfidentialNonce),
+        }
+    }
+
+    /// Parses a consensus encoded nonce, which must span the whole slice.
+    pub fn from_slice(mut bytes: &[u8]) -> Result<ConfidentialNonce, Error> {
+        let nonce = ConfidentialNonce::consensus_decode(&mut bytes)?;
+
+        if !bytes.is_empty() {
+            return Err(Error::InvalidConfidentialNonce);
+        }
+
+        Ok(nonce)
+    }
+
+    /// Returns whether the nonce is null.
+    pub fn is_null(&self) -> bool {
+        *self == ConfidentialNonce::Null
+    }
+
+    /// Returns the explicit nonce, if any.
+    pub fn explicit(&self) -> Option<[u8; 32]> {
+        match *self {
+            ConfidentialNonce::Explicit(nonce) => Some(nonce),
+            _ => None,
+        }
+    }
+
+    /// Returns the public key, if any.
+    pub fn commitment(&self) -> Option<PublicKey> {
+        match *self {
+            ConfidentialNonce::Confidential(pk) => Some(pk),
+            _ => None,
+        }
+    }
+}
+
+impl From<PublicKey> for ConfidentialNonce {
+    fn from(pk: PublicKey) -> Self {
+        ConfidentialNonce::Confidential(pk)
+    }
+}
+
+/// Verifies that the inputs and issuances of a transaction balance its outputs and fees.
+///
+/// Explicit values are turned into unblinded commitments using the generator of their asset,
+/// confidential values are used as-is. Null issuance amounts count as zero, while inputs and
+/// outputs must have a non-null asset and value.
+///
+/// If the transaction doesn't balance and all amounts are explicit, the error reports whether
+/// the inputs or the outputs are larger. Otherwise [`Error::UnbalancedTransaction`] is returned.
+pub fn verify_transaction_balance<C: Signing>(
+    secp: &Secp256k1<C>,
+    inputs: &[(ConfidentialAsset, ConfidentialValue)],
+    issuances: &[(Tag, ConfidentialValue)],
+    outputs: &[(ConfidentialAsset, ConfidentialValue)],
+    fees: &[(Tag, u64)],
+) -> Result<(), Error> {
+    let issuances = issuances
+        .iter()
+        .filter(|&&(_, value)| !value.is_null())
+        .map(|&(tag, value)| (ConfidentialAsset::Explicit(tag), value))
+        .collect::<Vec<_>>();
+    let fees = fees
+        .iter()
+        .map(|&(tag, value)| {
+            (
+                ConfidentialAsset::Explicit(tag),
+                ConfidentialValue::Explicit(value),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut input_commitments = Vec::with_capacity(inputs.len() + issuances.len());
+    for &(asset, value) in inputs.iter().chain(issuances.iter()) {
+        input_commitments
+            .push(balance_commitment(secp, asset, value).ok_or(Error::InvalidTransactionInput)?);
+    }
+    let mut output_commitments = Vec::with_capacity(outputs.len() + fees.len());
+    for &(asset, value) in outputs.iter().chain(fees.iter()) {
+        output_commitments
+            .push(balance_commitment(secp, asset, value).ok_or(Error::InvalidTransactionOutput)?);
+    }
+
+    if verify_commitments_sum_to_equal(secp, &input_commitments, &output_commitments) {
+        return Ok(());
+    }
+
+    // The side can only be determined if nothing is blinded.
+    let mut totals = BTreeMap::new();
+    for &(asset, value) in inputs.iter().chain(issuances.iter()) {
+        match (asset.explicit(), value.explicit()) {
+            (Some(tag), Some(value)) => {
+                totals.entry(tag).or_insert((0u128, 0u128)).0 += value as u128
+            }
+            _ => return Err(Error::UnbalancedTransaction),
+        }
+    }
+    for &(asset, value) in outputs.iter().chain(fees.iter()) {
+        match (asset.explicit(), value.explicit()) {
+            (Some(tag), Some(value)) => {
+                totals.entry(tag).or_insert((0u128, 0u128)).1 += value as u128
+            }
+            _ => return Err(Error::UnbalancedTransaction),
+        }
+    }
+
+    for &(input_total, output_total) in totals.values() {
+        if input_total > output_total {
+            return Err(Error::TransactionInputsExceedOutputs);
+        }
+        if input_total < output_total {
+            return Err(Error::TransactionOutputsExceedInputs);
+        }
+    }
+
+    Err(Error::UnbalancedTransaction)
+}
+
+/// Returns the commitment to `value` of `asset` used in balance checks, or `None` if either is null.
+fn balance_commitment<C: Signing>(
+    secp: &Secp256k1<C>,
+    asset: ConfidentialAsset,
+    value: ConfidentialValue,
+) -> Option<PedersenCommitment> {
+    let generator = match asset {
+        ConfidentialAsset::Null => return None,
+        ConfidentialAsset::Explicit(tag) => Generator::new_unblinded(secp, tag),
+        ConfidentialAsset::Confidential(generator) => generator,
+    };
+
+    match value {
+        ConfidentialValue::Null => None,
+        ConfidentialValue::Explicit(value) => {
+            Some(PedersenCommitment::new_unblinded(secp, value, generator))
+        }
+        ConfidentialValue::Confidential(commitment) => Some(commitment),
+    }
+}
+
+/// Implements hex `Display`/`FromStr` and serde based on the consensus encoding.
+macro_rules! impl_confidential_encoding {
+    ($thing:ident, $err:expr) => {
+        impl fmt::LowerHex for $thing {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for ch in self.serialize().iter() {
+                    write!(f, "{:02x}", *ch)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl fmt::Display for $thing {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::LowerHex::fmt(self, f)
+            }
+        }
+
+        impl str::FromStr for $thing {
+            type Err = Error;
+            fn from_str(s: &str) -> Result<$thing, Error> {
+                let mut res = [0; 33];
+                match from_hex(s, &mut res) {
+                    Ok(len) => $thing::from_slice(&res[..len]),
+                    _ => Err($err),
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $thing {
+            fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                if s.is_human_readable() {
+                    s.collect_str(self)
+                } else {
+                    s.serialize_bytes(&self.serialize())
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $thing {
+            fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                use serde_util;
+
+                if d.is_human_readable() {
+                    d.deserialize_str(serde_util::FromStrVisitor::new("an ASCII hex string"))
+                } else {
+                    d.deserialize_bytes(serde_util::BytesVisitor::new(
+                        "a bytestring",
+                        $thing::from_slice,
+                    ))
+                }
+            }
+        }
+    };
+}
+
+impl_confidential_encoding!(ConfidentialValue, Error::InvalidConfidentialValue);
+impl_confidential_encoding!(ConfidentialAsset, Error::InvalidConfidentialAsset);
+impl_confidential_encoding!(ConfidentialNonce, Error::InvalidConfidentialNonce);
+
+#[cfg(all(test, feature = "global-context"))]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use {Tweak, SECP256K1};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn test_confidential_value_encoding() {
+        let value: ConfidentialValue = "00".parse().unwrap();
+        assert_eq!(value, ConfidentialValue::Null);
+
+        let value: ConfidentialValue = "0100000000000003e8".parse().unwrap();
+        assert_eq!(value, ConfidentialValue::Explicit(1_000));
+        assert_eq!(value.explicit(), Some(1_000));
+        assert_eq!(value.to_string(), "0100000000000003e8");
+        assert_eq!(
+            ConfidentialValue::Explicit(0x0102030405060708).serialize(),
+            vec![1, 1, 2, 3, 4, 5, 6, 7, 8]
+        );
+
+        let generator = Generator::new_unblinded(SECP256K1, Tag::random());
+        let commitment =
+            PedersenCommitment::new(SECP256K1, 1_000, Tweak::new(&mut thread_rng()), generator);
+        let value = ConfidentialValue::from(commitment);
+        let bytes = value.serialize();
+        assert_eq!(bytes.len(), value.encoded_length());
+        assert_eq!(ConfidentialValue::from_slice(&bytes).unwrap(), value);
+        assert_eq!(value.commitment(), Some(commitment));
+
+        assert!(ConfidentialValue::from_slice(&[]).is_err());
+        assert!(ConfidentialValue::from_slice(&[0, 0]).is_err());
+        assert!(ConfidentialValue::from_slice(&[1, 0, 0]).is_err());
+        assert!(ConfidentialValue::from_slice(&[0x0a; 33]).is_err());
+    }
+
+    #[test]
+    fn test_confidential_asset_encoding() {
+        let tag = Tag::from([0x25; 32]);
+        let asset = ConfidentialAsset::from(tag);
+        assert_eq!(
+            asset.to_string(),
+            "012525252525252525252525252525252525252525252525252525252525252525"
+        );
+        assert_eq!(
+            asset.to_string().parse::<ConfidentialAsset>().unwrap(),
+            asset
+        );
+
+        let generator = Generator::new_blinded(SECP256K1, tag, Tweak::new(&mut thread_rng()));
+        let asset = ConfidentialAsset::from(generator);
+        let bytes = asset.serialize();
+        assert!(bytes[0] == 0x0a || bytes[0] == 0x0b);
+        assert_eq!(ConfidentialAsset::from_slice(&bytes).unwrap(), asset);
+        assert_eq!(asset.commitment(), Some(generator));
+
+        assert_eq!(
+            ConfidentialAsset::from_slice(&[0]).unwrap(),
+            ConfidentialAsset::Null
+        );
+        assert!(ConfidentialAsset::from_slice(&[0x08; 33]).is_err());
+    }
+
+    #[test]
+    fn test_confidential_nonce_encoding() {
+        let (_, pk) = SECP256K1.generate_keypair(&mut thread_rng());
+        let nonce = ConfidentialNonce::from(pk);
+        let bytes = nonce.serialize();
+        assert_eq!(&bytes[..], &pk.serialize()[..]);
+        assert_eq!(ConfidentialNonce::from_slice(&bytes).unwrap(), nonce);
+
+        let nonce = ConfidentialNonce::Explicit([7; 32]);
+        assert_eq!(
+            ConfidentialNonce::from_slice(&nonce.serialize()).unwrap(),
+            nonce
+        );
+
+        // consensus decoding consumes exactly one field
+        let mut stream = Vec::new();
+        ConfidentialNonce::Null
+            .consensus_encode(&mut stream)
+            .unwrap();
+        nonce.consensus_encode(&mut stream).unwrap();
+        let mut reader = &stream[..];
+        assert_eq!(
+            ConfidentialNonce::consensus_decode(&mut reader).unwrap(),
+            ConfidentialNonce::Null
+        );
+        assert_eq!(
+            ConfidentialNonce::consensus_decode(&mut reader).unwrap(),
+            nonce
+        );
+        assert!(reader.is_empty());
+
+        assert!(ConfidentialNonce::from_slice(&[0x04; 33]).is_err());
+    }
+
+    #[test]
+    fn test_verify_transaction_balance() {
+        let asset = Tag::random();
+        let fee_asset = Tag::random();
+        let explicit = |tag, value| {
+            (
+                ConfidentialAsset::Explicit(tag),
+                ConfidentialValue::Explicit(value),
+            )
+        };
+
+        // fully explicit
+        let inputs = [explicit(asset, 1_000), explicit(fee_asset, 100)];
+        let outputs = [explicit(asset, 1_500), explicit(fee_asset, 90)];
+        let issuances = [(asset, ConfidentialValue::Explicit(500))];
+        let fees = [(fee_asset, 10)];
+        assert_eq!(
+            verify_transaction_balance(SECP256K1, &inputs, &issuances, &outputs, &fees),
+            Ok(())
+        );
+        assert_eq!(
+            verify_transaction_balance(SECP256K1, &inputs, &issuances, &outputs, &[]),
+            Err(Error::TransactionInputsExceedOutputs)
+        );
+        assert_eq!(
+            verify_transaction_balance(SECP256K1, &inputs, &[], &outputs, &fees),
+            Err(Error::TransactionOutputsExceedInputs)
+        );
+        assert_eq!(
+            verify_transaction_balance(
+                SECP256K1,
+                &inputs,
+                &[(asset, ConfidentialValue::Null)],
+                &[explicit(asset, 1_000), explicit(fee_asset, 90)],
+                &fees
+            ),
+            Ok(())
+        );
+
+        // confidential
+        let input_abf = Tweak::new(&mut thread_rng());
+        let input_vbf = Tweak::new(&mut thread_rng());
+        let input_generator = Generator::new_blinded(SECP256K1, asset, input_abf);
+        let input = (
+            ConfidentialAsset::Confidential(input_generator),
+            ConfidentialValue::Confidential(PedersenCommitment::new(
+                SECP256K1,
+                1_000,
+                input_vbf,
+                input_generator,
+            )),
+        );
+        // reuse the input blinding factors, so that the remainder can be an explicit value of the blinded asset
+        let output_abf = input_abf;
+        let output_generator = Generator::new_blinded(SECP256K1, asset, output_abf);
+        let output_vbf = input_vbf;
+        let output = (
+            ConfidentialAsset::Confidential(output_generator),
+            ConfidentialValue::Confidential(PedersenCommitment::new(
+                SECP256K1,
+                990,
+                output_vbf,
+                output_generator,
+            )),
+        );
+        let change = (
+            ConfidentialAsset::Confidential(output_generator),
+            ConfidentialValue::Explicit(10),
+        );
+        assert_eq!(
+            verify_transaction_balance(SECP256K1, &[input], &[], &[output, change], &[]),
+            Ok(())
+        );
+        assert_eq!(
+            verify_transaction_balance(SECP256K1, &[input], &[], &[output], &[]),
+            Err(Error::UnbalancedTransaction)
+        );
+
+        assert_eq!(
+            verify_transaction_balance(
+                SECP256K1,
+                &[(ConfidentialAsset::Null, ConfidentialValue::Explicit(1))],
+                &[],
+                &[],
+                &[]
+            ),
+            Err(Error::InvalidTransactionInput)
+        );
+        assert_eq!(
+            verify_transaction_balance(
+                SECP256K1,
+                &[],
+                &[],
+                &[(ConfidentialAsset::Explicit(asset), ConfidentialValue::Null)],
+                &[]
+            ),
+            Err(Error::InvalidTransactionOutput)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_confidential_serde() {
+        use serde_test::Configure;
+        use serde_test::{assert_tokens, Token};
+
+        let value = ConfidentialValue::Explicit(1_000);
+        assert_tokens(&value.readable(), &[Token::Str("0100000000000003e8")]);
+        assert_tokens(
+            &value.compact(),
+            &[Token::Bytes(&[1, 0, 0, 0, 0, 0, 0, 0x03, 0xe8])],
+        );
+
+        let asset = ConfidentialAsset::Null;
+        assert_tokens(&asset.readable(), &[Token::Str("00")]);
+        assert_tokens(&asset.compact(), &[Token::Bytes(&[0])]);
+
+        let nonce = ConfidentialNonce::Explicit([0; 32]);
+        assert_tokens(
+            &nonce.readable(),
+            &[Token::Str(
+                "010000000000000000000000000000000000000000000000000000000000000000",
+            )],
+        );
+    }
+}
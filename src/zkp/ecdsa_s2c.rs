@@ -0,0 +1,703 @@
+//! # ECDSA Sign-to-Contract
+//! Support for ECDSA signatures that commit to some data inside their nonce.
+//!
+//! Sign-to-contract offsets the public nonce `R` of a signature by
+//! `hash(R, data) * G`. The resulting signature is a regular ECDSA signature,
+//! but together with the original nonce (the [`EcdsaS2cOpening`]) anyone can
+//! check that it commits to `data`.
+//!
+//! The module also implements the ECDSA Anti-Exfil Protocol on top of
+//! sign-to-contract, which allows a host to verify that a signing device did
+//! not use its nonces to leak the secret key. See
+//! https://github.com/ElementsProject/secp256k1-zkp/blob/725d895fc54cf82da1c2a9c69048656405da556d/include/secp256k1_ecdsa_s2c.h
+//! for details.
+//!
+
+use core::{fmt, str};
+use ffi::{self, CPtr};
+#[cfg(any(test, feature = "rand"))]
+use rand::{CryptoRng, Rng};
+use {constants, ecdsa::Signature, Message, PublicKey, Secp256k1, SecretKey};
+use {from_hex, Error};
+use {Context, Signing, Verification};
+
+/// Represents the opening of a sign-to-contract commitment, i.e. the public nonce
+/// the signer started with before committing to the data.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct EcdsaS2cOpening(ffi::EcdsaS2cOpening);
+
+impl EcdsaS2cOpening {
+    /// Parse a sign-to-contract opening from its 33-byte serialization.
+    pub fn from_slice(data: &[u8]) -> Result<EcdsaS2cOpening, Error> {
+        if data.len() != constants::PUBLIC_KEY_SIZE {
+            return Err(Error::InvalidEcdsaS2cOpening);
+        }
+
+        let mut opening = ffi::EcdsaS2cOpening::new();
+        let ret = unsafe {
+            ffi::secp256k1_ecdsa_s2c_opening_parse(
+                ffi::secp256k1_context_no_precomp,
+                &mut opening,
+                data.as_c_ptr(),
+            )
+        };
+
+        if ret != 1 {
+            return Err(Error::InvalidEcdsaS2cOpening);
+        }
+
+        Ok(EcdsaS2cOpening(opening))
+    }
+
+    /// Serialize the sign-to-contract opening.
+    ///
+    /// The format of this serialization is stable and platform-independent.
+    pub fn serialize(&self) -> [u8; constants::PUBLIC_KEY_SIZE] {
+        let mut output = [0u8; constants::PUBLIC_KEY_SIZE];
+
+        let ret = unsafe {
+            ffi::secp256k1_ecdsa_s2c_opening_serialize(
+                ffi::secp256k1_context_no_precomp,
+                output.as_mut_c_ptr(),
+                self.as_c_ptr(),
+            )
+        };
+        assert_eq!(ret, 1, "failed to serialize sign-to-contract opening");
+
+        output
+    }
+
+    /// Verifies that `sig` commits to `data` using this opening.
+    ///
+    /// Note that this does not check that `sig` is a valid signature for any
+    /// message or public key. Use [`Secp256k1::verify_ecdsa`] for that.
+    pub fn verify_commit<C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        sig: &Signature,
+        data: &[u8; 32],
+    ) -> Result<(), Error> {
+        let ret = unsafe {
+            ffi::secp256k1_ecdsa_s2c_verify_commit(
+                *secp.ctx(),
+                sig.as_c_ptr(),
+                data.as_c_ptr(),
+                self.as_c_ptr(),
+            )
+        };
+
+        if ret != 1 {
+            return Err(Error::CannotVerifyEcdsaS2cCommitment);
+        }
+
+        Ok(())
+    }
+
+    /// Obtains a raw const pointer suitable for use with FFI functions
+    #[inline]
+    pub fn as_ptr(&self) -> *const ffi::EcdsaS2cOpening {
+        &self.0
+    }
+
+    /// Obtains a raw mutable pointer suitable for use with FFI functions
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut ffi::EcdsaS2cOpening {
+        &mut self.0
+    }
+}
+
+/// Creates an ECDSA signature whose nonce commits to `data`.
+///
+/// Returns the signature along with the [`EcdsaS2cOpening`] that is needed to
+/// verify the commitment with [`EcdsaS2cOpening::verify_commit`].
+pub fn sign_with_contract<C: Signing>(
+    secp: &Secp256k1<C>,
+    msg: &Message,
+    sk: &SecretKey,
+    data: &[u8; 32],
+) -> (Signature, EcdsaS2cOpening) {
+    let mut opening = ffi::EcdsaS2cOpening::new();
+
+    unsafe {
+        let mut sig = ffi::Signature::new();
+        let res = ffi::secp256k1_ecdsa_s2c_sign(
+            *secp.ctx(),
+            &mut sig,
+            &mut opening,
+            msg.as_c_ptr(),
+            sk.as_c_ptr(),
+            data.as_c_ptr(),
+        );
+        // Only fails for an invalid secret key which cannot be constructed in safe rust.
+        debug_assert_eq!(res, 1);
+
+        (Signature::from(sig), EcdsaS2cOpening(opening))
+    }
+}
+
+impl fmt::LowerHex for EcdsaS2cOpening {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ch in self.serialize().iter() {
+            write!(f, "{:02x}", ch)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for EcdsaS2cOpening {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl str::FromStr for EcdsaS2cOpening {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<EcdsaS2cOpening, Error> {
+        let mut res = [0; constants::PUBLIC_KEY_SIZE];
+        match from_hex(s, &mut res) {
+            Ok(constants::PUBLIC_KEY_SIZE) => EcdsaS2cOpening::from_slice(&res),
+            _ => Err(Error::InvalidEcdsaS2cOpening),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for EcdsaS2cOpening {
+    fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.collect_str(self)
+        } else {
+            s.serialize_bytes(&self.serialize())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for EcdsaS2cOpening {
+    fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        use serde_util;
+
+        if d.is_human_readable() {
+            d.deserialize_str(serde_util::FromStrVisitor::new("an ASCII hex string"))
+        } else {
+            d.deserialize_bytes(serde_util::BytesVisitor::new(
+                "a bytestring",
+                EcdsaS2cOpening::from_slice,
+            ))
+        }
+    }
+}
+
+impl CPtr for EcdsaS2cOpening {
+    type Target = ffi::EcdsaS2cOpening;
+    fn as_c_ptr(&self) -> *const Self::Target {
+        self.as_ptr()
+    }
+
+    fn as_mut_c_ptr(&mut self) -> *mut Self::Target {
+        self.as_mut_ptr()
+    }
+}
+
+/// The host's commitment to its randomness, sent to the signer in the first
+/// step of the Anti-Exfil Protocol.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct AntiExfilHostCommitment([u8; 32]);
+
+/// The host's randomness, revealed to the signer after it has received the
+/// signer's commitment.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct AntiExfilHostRandomness([u8; 32]);
+
+/// The host side of the ECDSA Anti-Exfil Protocol.
+///
+/// The protocol runs as follows:
+/// 1. The host sends [`AntiExfilHost::commit`] to the signer.
+/// 2. The signer replies with [`AntiExfilSigner::commit`], an [`EcdsaS2cOpening`].
+/// 3. The host sends [`AntiExfilHost::randomness`] to the signer.
+/// 4. The signer replies with a signature created by [`AntiExfilSigner::sign`].
+/// 5. The host checks the signature with [`AntiExfilHost::verify`].
+///
+/// If the signer fails after step 2, the protocol must be restarted with the
+/// same randomness and the host must check that the signer proposes the same
+/// commitment again. Otherwise the signer can bias its nonces by selectively aborting.
+#[derive(Clone)]
+pub struct AntiExfilHost {
+    randomness: [u8; 32],
+}
+
+impl AntiExfilHost {
+    /// Creates a new host, drawing its randomness from the provided random number generator.
+    /// Requires compilation with "rand" feature.
+    #[cfg(any(test, feature = "rand"))]
+    pub fn new<R: Rng + CryptoRng>(rng: &mut R) -> AntiExfilHost {
+        let mut randomness = [0u8; 32];
+        rng.fill_bytes(&mut randomness);
+        AntiExfilHost::from_randomness(randomness)
+    }
+
+    /// Creates a new host from the given randomness. The randomness must come
+    /// from a cryptographically secure random number generator.
+    pub fn from_randomness(randomness: [u8; 32]) -> AntiExfilHost {
+        AntiExfilHost { randomness }
+    }
+
+    /// Computes the commitment to the host's randomness that is sent to the signer.
+    pub fn commit<C: Context>(&self, secp: &Secp256k1<C>) -> AntiExfilHostCommitment {
+        let mut commitment = [0u8; 32];
+
+        let ret = unsafe {
+            ffi::secp256k1_ecdsa_anti_exfil_host_commit(
+                *secp.ctx(),
+                commitment.as_mut_c_ptr(),
+                self.randomness.as_c_ptr(),
+            )
+        };
+        debug_assert_eq!(ret, 1);
+
+        AntiExfilHostCommitment(commitment)
+    }
+
+    /// Reveals the host's randomness. This must only be sent to the signer
+    /// after the signer's commitment has been received.
+    pub fn randomness(&self) -> AntiExfilHostRandomness {
+        AntiExfilHostRandomness(self.randomness)
+    }
+
+    /// Verifies that `sig` is a valid signature of `msg` for `pubkey` and that its
+    /// nonce commits to the host's randomness using the signer's commitment.
+    pub fn verify<C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        sig: &Signature,
+        msg: &Message,
+        pubkey: &PublicKey,
+        signer_commitment: &EcdsaS2cOpening,
+    ) -> Result<(), Error> {
+        let ret = unsafe {
+            ffi::secp256k1_anti_exfil_host_verify(
+                *secp.ctx(),
+                sig.as_c_ptr(),
+                msg.as_c_ptr(),
+                pubkey.as_c_ptr(),
+                self.randomness.as_c_ptr(),
+                signer_commitment.as_c_ptr(),
+            )
+        };
+
+        if ret != 1 {
+            return Err(Error::CannotVerifyAntiExfilSignature);
+        }
+
+        Ok(())
+    }
+}
+
+/// The signer side of the ECDSA Anti-Exfil Protocol.
+///
+/// The signer is stateless: it re-derives its original nonce from the host's
+/// randomness when signing, so it doesn't need to remember anything between
+/// [`AntiExfilSigner::commit`] and [`AntiExfilSigner::sign`].
+#[derive(Clone)]
+pub struct AntiExfilSigner {
+    sk: SecretKey,
+}
+
+impl AntiExfilSigner {
+    /// Creates a new signer for the given secret key.
+    pub fn new(sk: SecretKey) -> AntiExfilSigner {
+        AntiExfilSigner { sk }
+    }
+
+    /// Computes the signer's original nonce for `msg`, using the host's commitment
+    /// as auxiliary randomness. The result is sent to the host.
+    pub fn commit<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        msg: &Message,
+        host_commitment: &AntiExfilHostCommitment,
+    ) -> EcdsaS2cOpening {
+        let mut opening = ffi::EcdsaS2cOpening::new();
+
+        let ret = unsafe {
+            ffi::secp256k1_ecdsa_anti_exfil_signer_commit(
+                *secp.ctx(),
+                &mut opening,
+                msg.as_c_ptr(),
+                self.sk.as_c_ptr(),
+                host_commitment.0.as_c_ptr(),
+            )
+        };
+        debug_assert_eq!(ret, 1);
+
+        EcdsaS2cOpening(opening)
+    }
+
+    /// Signs `msg`, committing to the host's randomness in the nonce.
+    pub fn sign<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        msg: &Message,
+        host_randomness: &AntiExfilHostRandomness,
+    ) -> Signature {
+        unsafe {
+            let mut sig = ffi::Signature::new();
+            let ret = ffi::secp256k1_anti_exfil_sign(
+                *secp.ctx(),
+                &mut sig,
+                msg.as_c_ptr(),
+                self.sk.as_c_ptr(),
+                host_randomness.0.as_c_ptr(),
+            );
+            // Only fails for an invalid secret key which cannot be constructed in safe rust.
+            debug_assert_eq!(ret, 1);
+
+            Signature::from(sig)
+        }
+    }
+}
+
+impl AntiExfilHostCommitment {
+    /// Creates an [`AntiExfilHostCommitment`] directly from a slice
+    pub fn from_slice(data: &[u8]) -> Result<AntiExfilHostCommitment, Error> {
+        match data.len() {
+            32 => {
+                let mut ret = [0; 32];
+                ret[..].copy_from_slice(data);
+                Ok(AntiExfilHostCommitment(ret))
+            }
+            _ => Err(Error::InvalidAntiExfilHostData),
+        }
+    }
+
+    /// Serialize the commitment.
+    pub fn serialize(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl AntiExfilHostRandomness {
+    /// Creates an [`AntiExfilHostRandomness`] directly from a slice
+    pub fn from_slice(data: &[u8]) -> Result<AntiExfilHostRandomness, Error> {
+        match data.len() {
+            32 => {
+                let mut ret = [0; 32];
+                ret[..].copy_from_slice(data);
+                Ok(AntiExfilHostRandomness(ret))
+            }
+            _ => Err(Error::InvalidAntiExfilHostData),
+        }
+    }
+
+    /// Serialize the randomness.
+    pub fn serialize(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl fmt::LowerHex for AntiExfilHostCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ch in self.0.iter() {
+            write!(f, "{:02x}", ch)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AntiExfilHostCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl str::FromStr for AntiExfilHostCommitment {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<AntiExfilHostCommitment, Error> {
+        let mut res = [0; 32];
+        match from_hex(s, &mut res) {
+            Ok(32) => Ok(AntiExfilHostCommitment(res)),
+            _ => Err(Error::InvalidAntiExfilHostData),
+        }
+    }
+}
+
+impl fmt::LowerHex for AntiExfilHostRandomness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ch in self.0.iter() {
+            write!(f, "{:02x}", ch)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AntiExfilHostRandomness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl str::FromStr for AntiExfilHostRandomness {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<AntiExfilHostRandomness, Error> {
+        let mut res = [0; 32];
+        match from_hex(s, &mut res) {
+            Ok(32) => Ok(AntiExfilHostRandomness(res)),
+            _ => Err(Error::InvalidAntiExfilHostData),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for AntiExfilHostCommitment {
+    fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.collect_str(self)
+        } else {
+            s.serialize_bytes(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for AntiExfilHostCommitment {
+    fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        use serde_util;
+
+        if d.is_human_readable() {
+            d.deserialize_str(serde_util::FromStrVisitor::new("an ASCII hex string"))
+        } else {
+            d.deserialize_bytes(serde_util::BytesVisitor::new(
+                "a bytestring",
+                AntiExfilHostCommitment::from_slice,
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for AntiExfilHostRandomness {
+    fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.collect_str(self)
+        } else {
+            s.serialize_bytes(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for AntiExfilHostRandomness {
+    fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        use serde_util;
+
+        if d.is_human_readable() {
+            d.deserialize_str(serde_util::FromStrVisitor::new("an ASCII hex string"))
+        } else {
+            d.deserialize_bytes(serde_util::BytesVisitor::new(
+                "a bytestring",
+                AntiExfilHostRandomness::from_slice,
+            ))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "global-context"))]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, RngCore};
+    use SECP256K1;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn test_ecdsa_s2c_sign_and_verify() {
+        let mut rng = thread_rng();
+        let (sk, pk) = SECP256K1.generate_keypair(&mut rng);
+        let msg = Message::from_slice(&[2u8; 32]).unwrap();
+        let mut data = [0u8; 32];
+        rng.fill_bytes(&mut data);
+
+        let (sig, opening) = sign_with_contract(SECP256K1, &msg, &sk, &data);
+
+        SECP256K1
+            .verify_ecdsa(&msg, &sig, &pk)
+            .expect("signature to be valid");
+        opening
+            .verify_commit(SECP256K1, &sig, &data)
+            .expect("signature to commit to data");
+        opening
+            .verify_commit(SECP256K1, &sig, &[0xab; 32])
+            .expect_err("signature not to commit to other data");
+    }
+
+    #[test]
+    fn test_ecdsa_s2c_fixed_vectors() {
+        // Test vectors from libsecp256k1-zkp's `test_ecdsa_s2c_fixed_vectors`.
+        let sk = SecretKey::from_slice(&[0x55; 32]).unwrap();
+        let msg = Message::from_slice(&[0x88; 32]).unwrap();
+        let vectors = [
+            (
+                "1bf6fb42f41eb876c4d7aa0d67242b00baab99dc2084493e4e63277fa1f77f22",
+                "03f030def3188c0f56fcea87435b307643f45dafe22cbc82fd56034fae97417d3a",
+            ),
+            (
+                "35199a8fbf84ad6ef69a184c1b19285befbe06e60b6264e6d373893f6855e24a",
+                "03901717ce7c7484a2ce1b7dc7403b14e0354971393ec092a7f3e0c8e4e2d2639d",
+            ),
+        ];
+
+        for &(data, expected_opening) in vectors.iter() {
+            let mut buf = [0u8; 32];
+            from_hex(data, &mut buf).unwrap();
+
+            let (sig, opening) = sign_with_contract(SECP256K1, &msg, &sk, &buf);
+
+            assert_eq!(opening.to_string(), expected_opening);
+            opening.verify_commit(SECP256K1, &sig, &buf).unwrap();
+            SECP256K1
+                .verify_ecdsa(&msg, &sig, &PublicKey::from_secret_key(SECP256K1, &sk))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_s2c_opening_roundtrip() {
+        let opening: EcdsaS2cOpening =
+            "03f030def3188c0f56fcea87435b307643f45dafe22cbc82fd56034fae97417d3a"
+                .parse()
+                .unwrap();
+
+        let parsed = EcdsaS2cOpening::from_slice(&opening.serialize()).unwrap();
+        assert_eq!(parsed, opening);
+
+        assert_eq!(
+            EcdsaS2cOpening::from_slice(&[0x03; 32]),
+            Err(Error::InvalidEcdsaS2cOpening)
+        );
+        assert_eq!(
+            EcdsaS2cOpening::from_slice(&[0x04; 33]),
+            Err(Error::InvalidEcdsaS2cOpening)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ecdsa_s2c_opening_de_serialization() {
+        use serde_test::Configure;
+        use serde_test::{assert_tokens, Token};
+
+        let opening: EcdsaS2cOpening =
+            "03f030def3188c0f56fcea87435b307643f45dafe22cbc82fd56034fae97417d3a"
+                .parse()
+                .unwrap();
+
+        assert_tokens(
+            &opening.readable(),
+            &[Token::Str(
+                "03f030def3188c0f56fcea87435b307643f45dafe22cbc82fd56034fae97417d3a",
+            )],
+        );
+
+        assert_tokens(
+            &opening.compact(),
+            &[Token::Bytes(&[
+                3, 240, 48, 222, 243, 24, 140, 15, 86, 252, 234, 135, 67, 91, 48, 118, 67, 244, 93,
+                175, 226, 44, 188, 130, 253, 86, 3, 79, 174, 151, 65, 125, 58,
+            ])],
+        );
+    }
+
+    #[test]
+    fn test_anti_exfil_protocol() {
+        let mut rng = thread_rng();
+        let (sk, pk) = SECP256K1.generate_keypair(&mut rng);
+        let msg = Message::from_slice(&[2u8; 32]).unwrap();
+
+        let host = AntiExfilHost::new(&mut rng);
+        let signer = AntiExfilSigner::new(sk);
+
+        let host_commitment = host.commit(SECP256K1);
+        let signer_commitment = signer.commit(SECP256K1, &msg, &host_commitment);
+        let sig = signer.sign(SECP256K1, &msg, &host.randomness());
+
+        host.verify(SECP256K1, &sig, &msg, &pk, &signer_commitment)
+            .expect("anti-exfil signature to be valid");
+        SECP256K1
+            .verify_ecdsa(&msg, &sig, &pk)
+            .expect("signature to be valid");
+
+        // restarting the protocol with the same randomness yields the same commitment
+        assert_eq!(
+            signer_commitment,
+            signer.commit(SECP256K1, &msg, &host.commit(SECP256K1))
+        );
+
+        // a signer that ignores the host's randomness is caught
+        let other_host = AntiExfilHost::new(&mut rng);
+        let sig = signer.sign(SECP256K1, &msg, &other_host.randomness());
+        assert_eq!(
+            host.verify(SECP256K1, &sig, &msg, &pk, &signer_commitment),
+            Err(Error::CannotVerifyAntiExfilSignature)
+        );
+
+        // the signature must also be valid for the message
+        let sig = signer.sign(SECP256K1, &msg, &host.randomness());
+        let other_msg = Message::from_slice(&[3u8; 32]).unwrap();
+        assert_eq!(
+            host.verify(SECP256K1, &sig, &other_msg, &pk, &signer_commitment),
+            Err(Error::CannotVerifyAntiExfilSignature)
+        );
+    }
+
+    #[test]
+    fn test_anti_exfil_signer_commit_fixed_vectors() {
+        // Test vectors from libsecp256k1-zkp's `test_ecdsa_anti_exfil_signer_commit`.
+        let signer = AntiExfilSigner::new(SecretKey::from_slice(&[0x55; 32]).unwrap());
+        let msg = Message::from_slice(&[0x88; 32]).unwrap();
+        let vectors = [
+            (
+                "1bf6fb42f41eb876c4d7aa0d67242b00baab99dc2084493e4e63277fa1f77f22",
+                "02df63755d1f3292bffed82986b106497c93b1f8bdc0454b6b0b0a4779c0ef7188",
+            ),
+            (
+                "35199a8fbf84ad6ef69a184c1b19285befbe06e60b6264e6d373893f6855e24a",
+                "02c04ac7f771e8ebdbf315ff5e58b7fe9516102103500066172c4fac5b20f9e0ea",
+            ),
+        ];
+
+        for &(host_commitment, expected_opening) in vectors.iter() {
+            let host_commitment: AntiExfilHostCommitment = host_commitment.parse().unwrap();
+            let opening = signer.commit(SECP256K1, &msg, &host_commitment);
+
+            assert_eq!(opening.to_string(), expected_opening);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_anti_exfil_host_data_de_serialization() {
+        use serde_test::Configure;
+        use serde_test::{assert_tokens, Token};
+
+        let host = AntiExfilHost::from_randomness([0x2a; 32]);
+        let randomness = host.randomness();
+
+        assert_tokens(
+            &randomness.readable(),
+            &[Token::Str(
+                "2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a",
+            )],
+        );
+        assert_tokens(&randomness.compact(), &[Token::Bytes(&[0x2a; 32])]);
+
+        let commitment = AntiExfilHostCommitment::from_slice(&[0x17; 32]).unwrap();
+        assert_tokens(
+            &commitment.readable(),
+            &[Token::Str(
+                "1717171717171717171717171717171717171717171717171717171717171717",
+            )],
+        );
+        assert_tokens(&commitment.compact(), &[Token::Bytes(&[0x17; 32])]);
+    }
+}
@@ -1,4 +1,6 @@
-use core::{fmt, str};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use alloc::vec::Vec;
+use core::{fmt, ops, str};
 use ffi::{self, CPtr};
 #[cfg(feature = "rand")]
 use rand::Rng;
@@ -105,7 +107,63 @@ impl Tweak {
         }
         Ok(Tweak(data))
     }
+
+    /// Computes the sum of the `positive` tweaks minus the sum of the `negative` tweaks, modulo
+    /// the curve order.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn sum(positive: &[Tweak], negative: &[Tweak]) -> Tweak {
+        let blinds = positive
+            .iter()
+            .chain(negative.iter())
+            .map(|t| t.as_ptr())
+            .collect::<Vec<_>>();
+
+        Tweak::blind_sum(&blinds, positive.len())
+    }
+
+    fn blind_sum(blinds: &[*const u8], n_positive: usize) -> Tweak {
+        let mut ret = [0u8; constants::SECRET_KEY_SIZE];
+
+        let res = unsafe {
+            ffi::secp256k1_pedersen_blind_sum(
+                ffi::secp256k1_context_no_precomp,
+                ret.as_mut_c_ptr(),
+                blinds.as_ptr(),
+                blinds.len(),
+                n_positive,
+            )
+        };
+        // Only fails if a tweak is outside the curve order which cannot be constructed in safe rust.
+        assert_eq!(res, 1, "failed to sum tweaks");
+
+        Tweak(ret)
+    }
+}
+
+impl ops::Add for Tweak {
+    type Output = Tweak;
+
+    fn add(self, other: Tweak) -> Tweak {
+        Tweak::blind_sum(&[self.as_ptr(), other.as_ptr()], 2)
+    }
 }
+
+impl ops::Sub for Tweak {
+    type Output = Tweak;
+
+    fn sub(self, other: Tweak) -> Tweak {
+        Tweak::blind_sum(&[self.as_ptr(), other.as_ptr()], 1)
+    }
+}
+
+impl ops::Neg for Tweak {
+    type Output = Tweak;
+
+    fn neg(self) -> Tweak {
+        Tweak::blind_sum(&[self.as_ptr()], 0)
+    }
+}
+
 /// Represents a generator on the secp256k1 curve.
 ///
 /// A generator is a public key internally but has a slightly different serialization with the first byte being tweaked.
@@ -177,7 +235,6 @@ impl Generator {
     ///
     /// This is `pub(crate)` because generators have a different serialization from regular public keys.
     /// As such, certain invariants need to be upheld which is easier if we don't allow users to access the internal representation of generators.
-    #[cfg(feature = "std")] // for un-used warnings
     pub(crate) fn as_inner(&self) -> &ffi::PublicKey {
         &self.0
     }
@@ -266,5 +323,43 @@ impl<'de> ::serde::Deserialize<'de> for Tweak {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     // TODO: Test prefix of serialization
+
+    fn tweak(n: u8) -> Tweak {
+        let mut bytes = [0u8; 32];
+        bytes[31] = n;
+        Tweak::from_inner(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_tweak_arithmetic() {
+        let mut minus_one = constants::CURVE_ORDER;
+        minus_one[31] -= 1;
+        let minus_one = Tweak::from_inner(minus_one).unwrap();
+
+        assert_eq!(tweak(1) + tweak(2), tweak(3));
+        assert_eq!(tweak(3) - tweak(2), tweak(1));
+        assert_eq!(tweak(1) - tweak(2), minus_one);
+        assert_eq!(-tweak(1), minus_one);
+        assert_eq!(minus_one + tweak(2), tweak(1));
+        assert_eq!(-ZERO_TWEAK, ZERO_TWEAK);
+        assert_eq!(tweak(5) + -tweak(5), ZERO_TWEAK);
+    }
+
+    #[test]
+    fn test_tweak_sum() {
+        assert_eq!(Tweak::sum(&[], &[]), ZERO_TWEAK);
+        assert_eq!(Tweak::sum(&[tweak(4)], &[]), tweak(4));
+        assert_eq!(Tweak::sum(&[], &[tweak(4)]), -tweak(4));
+        assert_eq!(
+            Tweak::sum(&[tweak(1), tweak(2), tweak(3)], &[tweak(4), tweak(5)]),
+            -tweak(3)
+        );
+        assert_eq!(
+            Tweak::sum(&[tweak(10), tweak(20)], &[tweak(7)]),
+            tweak(10) + tweak(20) - tweak(7)
+        );
+    }
 }
@@ -0,0 +1,150 @@
+//! # Asset issuance
+//!
+//! Derivation of asset ids and reissuance token ids as done by Elements.
+//!
+//! The entropy of an issuance is the fast merkle root of the hash of the outpoint being spent and
+//! the contract hash. The asset id and the reissuance token id are in turn fast merkle roots of the
+//! entropy and a fixed second leaf. Fast merkle roots hash two 32-byte leaves with a single SHA256
+//! compression, i.e. they take the SHA256 midstate without padding.
+//!
+
+use core::fmt;
+use hashes::{sha256, sha256d, Hash, HashEngine};
+use Tag;
+
+/// The outpoint spent by the input that carries an issuance.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct OutPoint {
+    /// The id of the transaction, in internal byte order.
+    pub txid: [u8; 32],
+    /// The index of the output.
+    pub vout: u32,
+}
+
+/// The entropy an asset id and its reissuance token are derived from.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
+pub struct AssetEntropy([u8; 32]);
+
+impl AssetEntropy {
+    /// Computes the entropy of a new issuance spending `outpoint` and committing to `contract_hash`.
+    pub fn new(outpoint: OutPoint, contract_hash: [u8; 32]) -> AssetEntropy {
+        let mut engine = sha256d::Hash::engine();
+        engine.input(&outpoint.txid);
+        engine.input(&[
+            outpoint.vout as u8,
+            (outpoint.vout >> 8) as u8,
+            (outpoint.vout >> 16) as u8,
+            (outpoint.vout >> 24) as u8,
+        ]);
+        let outpoint_hash = sha256d::Hash::from_engine(engine);
+
+        AssetEntropy(fast_merkle_root(
+            &outpoint_hash.into_inner(),
+            &contract_hash,
+        ))
+    }
+
+    /// Creates entropy from its raw bytes, e.g. the asset entropy field of a reissuance.
+    pub fn from_inner(inner: [u8; 32]) -> AssetEntropy {
+        AssetEntropy(inner)
+    }
+
+    /// Returns the raw bytes of the entropy.
+    pub fn into_inner(self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for AssetEntropy {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::LowerHex for AssetEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ch in self.0.iter() {
+            write!(f, "{:02x}", ch)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AssetEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl Tag {
+    /// Computes the asset id of an issuance from its entropy.
+    pub fn from_issuance_entropy(entropy: AssetEntropy) -> Tag {
+        Tag::from(fast_merkle_root(&entropy.0, &[0; 32]))
+    }
+
+    /// Computes the id of the reissuance token of an issuance from its entropy.
+    ///
+    /// The token id depends on whether the issued amount is blinded.
+    pub fn reissuance_token(entropy: AssetEntropy, confidential: bool) -> Tag {
+        let mut second_leaf = [0; 32];
+        second_leaf[0] = if confidential { 2 } else { 1 };
+
+        Tag::from(fast_merkle_root(&entropy.0, &second_leaf))
+    }
+}
+
+/// Computes the fast merkle root of two leaves, which is the SHA256 midstate after compressing them.
+fn fast_merkle_root(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut engine = sha256::Hash::engine();
+    engine.input(left);
+    engine.input(right);
+
+    engine.midstate().into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex_rev(s: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        ::from_hex(s, &mut bytes).unwrap();
+        bytes.reverse();
+        bytes
+    }
+
+    #[test]
+    fn test_issuance_derivation() {
+        // Test vector from rust-elements, hex strings are in display (reversed) byte order.
+        let outpoint = OutPoint {
+            txid: from_hex_rev("05a047c98e82a848dee94efcf32462b065198bebf2404d201ba2e06db30b28f4"),
+            vout: 0,
+        };
+        let entropy = AssetEntropy::new(outpoint, [0; 32]);
+        assert_eq!(
+            entropy.into_inner(),
+            from_hex_rev("746f447f691323502cad2ef646f932613d37a83aeaa2133185b316648df4b70a")
+        );
+
+        let asset = Tag::from_issuance_entropy(entropy);
+        assert_eq!(
+            <[u8; 32]>::from(asset),
+            from_hex_rev("dcd60818d863b5c026c40b2bc3ba6fdaf5018bcc8606c18adf7db4da0bcd8533")
+        );
+
+        let token = Tag::reissuance_token(entropy, false);
+        assert_eq!(
+            <[u8; 32]>::from(token),
+            from_hex_rev("c1adb114f4f87d33bf9ce90dd4f9ca523dd414d6cd010a7917903e2009689530")
+        );
+
+        // Computed with a standalone SHA256 compression following Elements'
+        // `CalculateReissuanceToken`, which reproduces the asset id and explicit token above.
+        let confidential_token = Tag::reissuance_token(entropy, true);
+        assert_eq!(
+            <[u8; 32]>::from(confidential_token),
+            from_hex_rev("d08425cac1a728360ae7c8aad2b21e9a04d1ab1c09959562661e5f13d9c5f803")
+        );
+        assert_ne!(confidential_token, token);
+    }
+}
@@ -1,28 +1,33 @@
+#[cfg(all(feature = "std", feature = "bitcoin_hashes"))]
+mod blind;
+#[cfg(feature = "std")]
+mod confidential;
 mod ecdsa_adaptor;
+mod ecdsa_s2c;
 mod generator;
-#[cfg(feature = "std")]
+#[cfg(feature = "bitcoin_hashes")]
+mod issuance;
 mod musig;
-#[cfg(feature = "std")]
 pub use self::musig::new_musig_nonce_pair;
 
-#[cfg(feature = "std")]
 mod pedersen;
-#[cfg(feature = "std")]
 mod rangeproof;
-#[cfg(feature = "std")]
 mod surjection_proof;
 mod tag;
 mod whitelist;
 
+#[cfg(all(feature = "std", feature = "bitcoin_hashes"))]
+pub use self::blind::*;
+#[cfg(feature = "std")]
+pub use self::confidential::*;
 pub use self::ecdsa_adaptor::*;
+pub use self::ecdsa_s2c::*;
 pub use self::generator::*;
-#[cfg(feature = "std")]
+#[cfg(feature = "bitcoin_hashes")]
+pub use self::issuance::*;
 pub use self::musig::*;
-#[cfg(feature = "std")]
 pub use self::pedersen::*;
-#[cfg(feature = "std")]
 pub use self::rangeproof::*;
-#[cfg(feature = "std")]
 pub use self::surjection_proof::*;
 pub use self::tag::*;
 pub use self::whitelist::*;
@@ -1,4 +1,3 @@
-use core::fmt;
 ///! This module implements high-level Rust bindings for a Schnorr-based
 ///! multi-signature scheme called MuSig2 (https://eprint.iacr.org/2020/1261).
 ///! It is compatible with bip-schnorr.
@@ -10,19 +9,39 @@ use core::fmt;
 ///! for users to begin using the library. A full description of the C API usage can be found
 ///! in [C-musig.md](secp256k1-sys/depend/secp256k1/src/modules/musig/musig.md), and Rust API
 ///! usage can be found in [Rust-musig.md](USAGE.md).
-use {core, std};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use alloc::vec::Vec;
+use core;
+use core::fmt;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::str;
+#[cfg(feature = "std")]
+use std;
 
 use ffi::{self, CPtr};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use from_hex;
+#[cfg(any(test, feature = "rand"))]
+use rand::{CryptoRng, Rng};
 use secp256k1::Parity;
 use ZERO_TWEAK;
 use {schnorr, KeyPair, XOnlyPublicKey};
 use {Message, PublicKey, Secp256k1, SecretKey, Tweak};
 use {Signing, Verification};
 
+/// Version of the encoding of [`MusigKeyAggCacheState`] and [`MusigSessionState`].
+#[cfg(any(feature = "std", feature = "alloc"))]
+const ENCODING_VERSION: u8 = 1;
+
 ///  Data structure containing auxiliary data generated in `pubkey_agg` and
 ///  required for `session_*_init`.
+///
+/// The cache wraps an opaque structure of libsecp256k1-zkp and has no encoding of its own. Build
+/// it with [`MusigKeyAggCacheState`] instead, which has a versioned `serialize`/`from_slice`
+/// encoding, hex `Display`/`FromStr` and serde support, and turn that back into a cache with
+/// [`MusigKeyAggCacheState::key_agg_cache`].
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct MusigKeyAggCache(ffi::MusigKeyaggCache, XOnlyPublicKey);
+pub struct MusigKeyAggCache(ffi::MusigKeyaggCache, XOnlyPublicKey, PublicKey);
 
 impl CPtr for MusigKeyAggCache {
     type Target = ffi::MusigKeyaggCache;
@@ -76,8 +95,14 @@ impl MusigKeyAggCache {
     /// let _agg_pk = key_agg_cache.agg_pk();
     /// # }
     /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
     pub fn new<C: Verification>(secp: &Secp256k1<C>, pubkeys: &[XOnlyPublicKey]) -> Self {
-        let cx = *secp.ctx();
+        MusigKeyAggCache::new_with_context(*secp.ctx(), pubkeys)
+    }
+
+    /// Aggregates `pubkeys` using the raw context `cx`.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn new_with_context(cx: *const ffi::Context, pubkeys: &[XOnlyPublicKey]) -> Self {
         let xonly_ptrs = pubkeys.iter().map(|k| k.as_ptr()).collect::<Vec<_>>();
         let mut key_agg_cache = ffi::MusigKeyaggCache::new();
 
@@ -98,7 +123,13 @@ impl MusigKeyAggCache {
                 // Returns 0 only if the keys are malformed that never happens in safe rust type system.
                 unreachable!("Invalid XOnlyPublicKey in input pubkeys")
             } else {
-                MusigKeyAggCache(key_agg_cache, agg_pk)
+                let mut agg_pk_full = PublicKey::from(ffi::PublicKey::new());
+                if ffi::secp256k1_musig_pubkey_get(cx, agg_pk_full.as_mut_ptr(), &mut key_agg_cache)
+                    == 0
+                {
+                    unreachable!("Key aggregation cache initialized by pubkey_agg")
+                }
+                MusigKeyAggCache(key_agg_cache, agg_pk, agg_pk_full)
             }
         }
     }
@@ -108,6 +139,39 @@ impl MusigKeyAggCache {
         self.1
     }
 
+    /// Obtains the aggregate public key for this [`MusigKeyAggCache`] as a full [`PublicKey`].
+    ///
+    /// Unlike [`MusigKeyAggCache::agg_pk`] this keeps the parity of the aggregate key, which is
+    /// needed e.g. to derive child keys from the aggregate key via BIP32. Like `agg_pk`, it is the
+    /// key before any tweaks were applied, see [`MusigKeyAggCache::tweaked_pk_full`] for the
+    /// tweaked key.
+    pub fn agg_pk_full(&self) -> PublicKey {
+        self.2
+    }
+
+    /// Obtains the aggregate public key with all tweaks applied so far as a full [`PublicKey`].
+    ///
+    /// This is the key the partial signatures of a session with this cache are valid for. Without
+    /// tweaks it equals [`MusigKeyAggCache::agg_pk_full`].
+    pub fn tweaked_pk_full(&self) -> PublicKey {
+        // The C API takes the cache as mutable but doesn't change it, so work on a copy
+        let mut cache = self.0;
+        unsafe {
+            let mut pk = PublicKey::from(ffi::PublicKey::new());
+            if ffi::secp256k1_musig_pubkey_get(
+                ffi::secp256k1_context_no_precomp,
+                pk.as_mut_ptr(),
+                &mut cache,
+            ) == 0
+            {
+                // Only fails when the arguments are invalid which is not possible in safe rust
+                unreachable!("Arguments must be valid and well-typed")
+            } else {
+                pk
+            }
+        }
+    }
+
     /// Apply ordinary "EC" tweaking to a public key in a [`MusigKeyAggCache`] by
     /// adding the generator multiplied with `tweak32` to it. Returns the tweaked [`PublicKey`].
     /// This is useful for deriving child keys from an aggregate public key via BIP32.
@@ -117,6 +181,10 @@ impl MusigKeyAggCache {
     /// to create a signature for it, use [`secp256k1::PublicKey::add_exp_assign`]
     /// instead.
     ///
+    /// The cache doesn't record which tweaks were applied to it. If you need to list them later,
+    /// e.g. to persist the cache, tweak through [`MusigKeyAggCacheState::pubkey_ec_tweak_add`]
+    /// instead.
+    ///
     /// # Arguments:
     ///
     /// * `secp` : [`Secp256k1`] context object initialized for verification
@@ -151,7 +219,15 @@ impl MusigKeyAggCache {
         secp: &Secp256k1<C>,
         tweak: SecretKey,
     ) -> Result<PublicKey, MusigTweakErr> {
-        let cx = *secp.ctx();
+        self.ec_tweak_add_with_context(*secp.ctx(), tweak)
+    }
+
+    /// Applies an ordinary "EC" tweak using the raw context `cx`.
+    fn ec_tweak_add_with_context(
+        &mut self,
+        cx: *const ffi::Context,
+        tweak: SecretKey,
+    ) -> Result<PublicKey, MusigTweakErr> {
         unsafe {
             let mut out = PublicKey::from(ffi::PublicKey::new());
             if ffi::secp256k1_musig_pubkey_ec_tweak_add(
@@ -176,6 +252,10 @@ impl MusigKeyAggCache {
     /// On the other hand, if you are only computing a public key, but not intending
     /// to create a signature for it, you can just use [`XOnlyPublicKey::tweak_add_assign`]
     ///
+    /// The cache doesn't record which tweaks were applied to it. If you need to list them later,
+    /// e.g. to persist the cache, tweak through [`MusigKeyAggCacheState::pubkey_xonly_tweak_add`]
+    /// instead.
+    ///
     /// # Arguments:
     ///
     /// * `secp` : [`Secp256k1`] context object initialized for verification
@@ -210,7 +290,15 @@ impl MusigKeyAggCache {
         secp: &Secp256k1<C>,
         tweak: SecretKey,
     ) -> Result<XOnlyPublicKey, MusigTweakErr> {
-        let cx = *secp.ctx();
+        self.xonly_tweak_add_with_context(*secp.ctx(), tweak)
+    }
+
+    /// Applies an "x-only" tweak using the raw context `cx`.
+    fn xonly_tweak_add_with_context(
+        &mut self,
+        cx: *const ffi::Context,
+        tweak: SecretKey,
+    ) -> Result<XOnlyPublicKey, MusigTweakErr> {
         unsafe {
             let mut out = XOnlyPublicKey::from(ffi::XOnlyPublicKey::new());
             if ffi::secp256k1_musig_pubkey_xonly_tweak_add(
@@ -317,6 +405,207 @@ impl MusigKeyAggCache {
     }
 }
 
+/// A tweak applied to the aggregate public key of a [`MusigKeyAggCacheState`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MusigTweak {
+    /// Tweak applied with [`MusigKeyAggCacheState::pubkey_ec_tweak_add`].
+    Ec(SecretKey),
+    /// Tweak applied with [`MusigKeyAggCacheState::pubkey_xonly_tweak_add`].
+    XOnly(SecretKey),
+}
+
+/// A [`MusigKeyAggCache`] together with the public keys and tweaks it was created from.
+///
+/// The cache itself wraps an opaque structure of libsecp256k1-zkp. The state can be persisted with
+/// [`MusigKeyAggCacheState::serialize`] and rebuilt with [`MusigKeyAggCacheState::from_slice`],
+/// e.g. by a coordinator that restarts between the signing rounds.
+///
+/// Tweak the aggregate key through the state rather than through the cache if you need to know
+/// the applied tweaks later: unlike [`MusigKeyAggCache`], the state records them and lists them
+/// in [`MusigKeyAggCacheState::tweaks`].
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MusigKeyAggCacheState {
+    key_agg_cache: MusigKeyAggCache,
+    pubkeys: Vec<XOnlyPublicKey>,
+    tweaks: Vec<MusigTweak>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl MusigKeyAggCacheState {
+    /// Aggregates `pubkeys` like [`MusigKeyAggCache::new`] and keeps them for serialization.
+    pub fn new<C: Verification>(secp: &Secp256k1<C>, pubkeys: &[XOnlyPublicKey]) -> Self {
+        MusigKeyAggCacheState {
+            key_agg_cache: MusigKeyAggCache::new(secp, pubkeys),
+            pubkeys: pubkeys.to_vec(),
+            tweaks: Vec::new(),
+        }
+    }
+
+    /// Applies an "EC" tweak like [`MusigKeyAggCache::pubkey_ec_tweak_add`] and records it.
+    pub fn pubkey_ec_tweak_add<C: Verification>(
+        &mut self,
+        secp: &Secp256k1<C>,
+        tweak: SecretKey,
+    ) -> Result<PublicKey, MusigTweakErr> {
+        self.ec_tweak_add_with_context(*secp.ctx(), tweak)
+    }
+
+    fn ec_tweak_add_with_context(
+        &mut self,
+        cx: *const ffi::Context,
+        tweak: SecretKey,
+    ) -> Result<PublicKey, MusigTweakErr> {
+        let tweaked = self.key_agg_cache.ec_tweak_add_with_context(cx, tweak)?;
+        self.tweaks.push(MusigTweak::Ec(tweak));
+        Ok(tweaked)
+    }
+
+    /// Applies an "x-only" tweak like [`MusigKeyAggCache::pubkey_xonly_tweak_add`] and records it.
+    pub fn pubkey_xonly_tweak_add<C: Verification>(
+        &mut self,
+        secp: &Secp256k1<C>,
+        tweak: SecretKey,
+    ) -> Result<XOnlyPublicKey, MusigTweakErr> {
+        self.xonly_tweak_add_with_context(*secp.ctx(), tweak)
+    }
+
+    fn xonly_tweak_add_with_context(
+        &mut self,
+        cx: *const ffi::Context,
+        tweak: SecretKey,
+    ) -> Result<XOnlyPublicKey, MusigTweakErr> {
+        let tweaked = self.key_agg_cache.xonly_tweak_add_with_context(cx, tweak)?;
+        self.tweaks.push(MusigTweak::XOnly(tweak));
+        Ok(tweaked)
+    }
+
+    /// The key aggregation cache with all tweaks applied.
+    pub fn key_agg_cache(&self) -> MusigKeyAggCache {
+        self.key_agg_cache
+    }
+
+    /// The public keys in the order they were aggregated.
+    pub fn pubkeys(&self) -> &[XOnlyPublicKey] {
+        &self.pubkeys
+    }
+
+    /// The tweaks applied so far through this state, in the order they were applied.
+    ///
+    /// Tweaks applied directly to a [`MusigKeyAggCache`] aren't recorded and don't show up here.
+    pub fn tweaks(&self) -> &[MusigTweak] {
+        &self.tweaks
+    }
+
+    /// Serializes the state such that it can be persisted and rebuilt.
+    ///
+    /// The encoding is a version byte, followed by the number of public keys as a 4-byte little
+    /// endian integer and the 32-byte public keys in aggregation order, followed by the number of
+    /// tweaks and the tweaks in the order they were applied. Each tweak is a byte that is 0 for an
+    /// "EC" tweak and 1 for an "x-only" tweak, followed by the 32-byte tweak.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(9 + 32 * self.pubkeys.len() + 33 * self.tweaks.len());
+        data.push(ENCODING_VERSION);
+        push_len(&mut data, self.pubkeys.len());
+        for pubkey in &self.pubkeys {
+            data.extend_from_slice(&pubkey.serialize());
+        }
+        push_len(&mut data, self.tweaks.len());
+        for tweak in &self.tweaks {
+            let (kind, tweak) = match *tweak {
+                MusigTweak::Ec(tweak) => (0, tweak),
+                MusigTweak::XOnly(tweak) => (1, tweak),
+            };
+            data.push(kind);
+            data.extend_from_slice(tweak.as_ref());
+        }
+        data
+    }
+
+    /// Parses a state serialized with [`MusigKeyAggCacheState::serialize`].
+    ///
+    /// The cache is rebuilt by aggregating the public keys and applying the tweaks again, so that
+    /// corrupted data can't result in an inconsistent cache.
+    ///
+    /// # Errors:
+    ///
+    /// - UnsupportedVersion: If the data was serialized with an unknown version of the encoding
+    /// - MalformedArg: If the data is truncated, has trailing bytes, has no public keys, or
+    ///   contains an invalid public key or tweak
+    pub fn from_slice(data: &[u8]) -> Result<Self, ParseError> {
+        let mut data = data;
+        let version = take(&mut data, 1)?[0];
+        if version != ENCODING_VERSION {
+            return Err(ParseError::UnsupportedVersion(version));
+        }
+
+        let n_pubkeys = take_len(&mut data)?;
+        if n_pubkeys == 0 {
+            return Err(ParseError::MalformedArg);
+        }
+        let mut pubkeys = Vec::new();
+        for _ in 0..n_pubkeys {
+            let pubkey = XOnlyPublicKey::from_slice(take(&mut data, 32)?)
+                .map_err(|_| ParseError::MalformedArg)?;
+            pubkeys.push(pubkey);
+        }
+        // Parsing doesn't require precomputation, and neither does key aggregation or tweaking.
+        let cx = unsafe { ffi::secp256k1_context_no_precomp };
+        let mut state = MusigKeyAggCacheState {
+            key_agg_cache: MusigKeyAggCache::new_with_context(cx, &pubkeys),
+            pubkeys,
+            tweaks: Vec::new(),
+        };
+
+        let n_tweaks = take_len(&mut data)?;
+        for _ in 0..n_tweaks {
+            let kind = take(&mut data, 1)?[0];
+            let tweak = SecretKey::from_slice(take(&mut data, 32)?)
+                .map_err(|_| ParseError::MalformedArg)?;
+            let res = match kind {
+                0 => state.ec_tweak_add_with_context(cx, tweak).map(|_| ()),
+                1 => state.xonly_tweak_add_with_context(cx, tweak).map(|_| ()),
+                _ => return Err(ParseError::MalformedArg),
+            };
+            res.map_err(|_| ParseError::MalformedArg)?;
+        }
+        if !data.is_empty() {
+            return Err(ParseError::MalformedArg);
+        }
+        Ok(state)
+    }
+}
+
+/// Appends `len` as a 4-byte little endian integer.
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn push_len(data: &mut Vec<u8>, len: usize) {
+    let len = len as u32;
+    data.extend_from_slice(&[
+        len as u8,
+        (len >> 8) as u8,
+        (len >> 16) as u8,
+        (len >> 24) as u8,
+    ]);
+}
+
+/// Splits off the first `len` bytes of `data`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], ParseError> {
+    if data.len() < len {
+        return Err(ParseError::MalformedArg);
+    }
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+/// Splits off a length written by [`push_len`].
+#[cfg(any(feature = "std", feature = "alloc"))]
+fn take_len(data: &mut &[u8]) -> Result<usize, ParseError> {
+    let b = take(data, 4)?;
+    Ok((b[0] as usize) | (b[1] as usize) << 8 | (b[2] as usize) << 16 | (b[3] as usize) << 24)
+}
+
 /// Musig tweaking related errors.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub enum MusigTweakErr {
@@ -648,6 +937,8 @@ pub enum ParseError {
     /// Parse Argument is malformed. This might occur if the point is on the secp order,
     /// or if the secp scalar is outside of group order
     MalformedArg,
+    /// The data was serialized with an unsupported version of the encoding
+    UnsupportedVersion(u8),
 }
 
 #[cfg(feature = "std")]
@@ -660,10 +951,78 @@ impl fmt::Display for ParseError {
                 write!(f, "Argument must be {} bytes, got {}", expected, got)
             }
             ParseError::MalformedArg => write!(f, "Malformed parse argument"),
+            ParseError::UnsupportedVersion(v) => write!(f, "Unsupported encoding version {}", v),
         }
     }
 }
 
+/// Implements hex `Display`/`FromStr` and serde based on `serialize` and `from_slice`.
+macro_rules! impl_musig_encoding {
+    ($thing:ident) => {
+        impl fmt::LowerHex for $thing {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for ch in self.serialize().iter() {
+                    write!(f, "{:02x}", *ch)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl fmt::Display for $thing {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::LowerHex::fmt(self, f)
+            }
+        }
+
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        impl str::FromStr for $thing {
+            type Err = ParseError;
+            fn from_str(s: &str) -> Result<$thing, ParseError> {
+                let mut res = vec![0; s.len() / 2];
+                match from_hex(s, &mut res) {
+                    Ok(len) => $thing::from_slice(&res[..len]),
+                    _ => Err(ParseError::MalformedArg),
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $thing {
+            fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                if s.is_human_readable() {
+                    s.collect_str(self)
+                } else {
+                    s.serialize_bytes(&self.serialize()[..])
+                }
+            }
+        }
+
+        #[cfg(all(any(feature = "std", feature = "alloc"), feature = "serde"))]
+        impl<'de> ::serde::Deserialize<'de> for $thing {
+            fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                use serde_util;
+
+                if d.is_human_readable() {
+                    d.deserialize_str(serde_util::FromStrVisitor::new("an ASCII hex string"))
+                } else {
+                    d.deserialize_bytes(serde_util::BytesVisitor::new(
+                        "a bytestring",
+                        $thing::from_slice,
+                    ))
+                }
+            }
+        }
+    };
+}
+
+impl_musig_encoding!(MusigPartialSignature);
+impl_musig_encoding!(MusigPubNonce);
+impl_musig_encoding!(MusigAggNonce);
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl_musig_encoding!(MusigKeyAggCacheState);
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl_musig_encoding!(MusigSessionState);
+
 /// Creates a signature from a pre-signature(not to be confused with [`MusigPartialSignature`])
 /// and an adaptor.
 ///
@@ -1063,7 +1422,8 @@ impl MusigPubNonce {
 
 /// Opaque data structure that holds a MuSig aggregated nonce.
 ///
-/// There are no serialization and parsing functions (yet).
+/// Serialized and parsed with [`MusigAggNonce::serialize`] and
+/// [`MusigAggNonce::from_slice`].
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct MusigAggNonce(ffi::MusigAggNonce);
 
@@ -1120,6 +1480,7 @@ impl MusigAggNonce {
     /// let aggnonce = MusigAggNonce::new(&secp, &[pub_nonce1, pub_nonce2]);
     /// # }
     /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
     pub fn new<C: Signing>(secp: &Secp256k1<C>, nonces: &[MusigPubNonce]) -> Self {
         let mut aggnonce = MusigAggNonce(ffi::MusigAggNonce::new());
         let nonce_ptrs = nonces.iter().map(|n| n.as_ptr()).collect::<Vec<_>>();
@@ -1245,6 +1606,11 @@ impl MusigAggNonce {
 
 /// Musig session data structure containing the
 /// secret and public nonce used in a multi-signature signing session
+///
+/// The session wraps an opaque structure of libsecp256k1-zkp and has no encoding of its own.
+/// Serialize the [`MusigSessionState`] it is created from instead, which has a versioned
+/// `serialize`/`from_slice` encoding, hex `Display`/`FromStr` and serde support, and rebuild the
+/// session with [`MusigSessionState::session`].
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct MusigSession(ffi::MusigSession);
 
@@ -1619,6 +1985,7 @@ impl MusigSession {
     /// assert!(secp.verify_schnorr(&schnorr_sig, &msg, &agg_pk).is_ok())
     /// # }
     /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
     pub fn partial_sig_agg(&self, partial_sigs: &[MusigPartialSignature]) -> schnorr::Signature {
         let part_sigs = partial_sigs.iter().map(|s| s.as_ptr()).collect::<Vec<_>>();
         let mut sig = [0u8; 64];
@@ -1699,6 +2066,89 @@ impl MusigSession {
     }
 }
 
+/// The public inputs a [`MusigSession`] is created from.
+///
+/// The session itself wraps an opaque structure of libsecp256k1-zkp. The state can be persisted with
+/// [`MusigSessionState::serialize`] and turned into a session again with [`MusigSessionState::session`].
+/// It doesn't contain the key aggregation cache, which is persisted with [`MusigKeyAggCacheState`].
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct MusigSessionState {
+    /// The aggregate nonce of all signers.
+    pub agg_nonce: MusigAggNonce,
+    /// The message to be signed.
+    pub msg: Message,
+    /// The adaptor of an adaptor signature session, see [`MusigSession::new`].
+    pub adaptor: Option<PublicKey>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl MusigSessionState {
+    /// Creates the session with `key_agg_cache`, see [`MusigSession::new`].
+    pub fn session<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        key_agg_cache: &MusigKeyAggCache,
+    ) -> MusigSession {
+        MusigSession::new(secp, key_agg_cache, self.agg_nonce, self.msg, self.adaptor)
+    }
+
+    /// Serializes the state such that it can be persisted.
+    ///
+    /// The encoding is a version byte, followed by the aggregate nonce, the message, and a byte
+    /// that is 1 if the session has an adaptor and 0 otherwise, followed by the 33-byte adaptor if
+    /// present. The state contains no secret data.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![ENCODING_VERSION];
+        data.extend_from_slice(&self.agg_nonce.serialize());
+        data.extend_from_slice(self.msg.as_ref());
+        match self.adaptor {
+            Some(adaptor) => {
+                data.push(1);
+                data.extend_from_slice(&adaptor.serialize());
+            }
+            None => data.push(0),
+        }
+        data
+    }
+
+    /// Parses a state serialized with [`MusigSessionState::serialize`].
+    ///
+    /// # Errors:
+    ///
+    /// - UnsupportedVersion: If the data was serialized with an unknown version of the encoding
+    /// - MalformedArg: If the data is truncated, has trailing bytes, or contains an invalid
+    ///   aggregate nonce or adaptor
+    pub fn from_slice(data: &[u8]) -> Result<Self, ParseError> {
+        let mut data = data;
+        let version = take(&mut data, 1)?[0];
+        if version != ENCODING_VERSION {
+            return Err(ParseError::UnsupportedVersion(version));
+        }
+
+        let agg_nonce = MusigAggNonce::from_slice(take(&mut data, ffi::MUSIG_AGGNONCE_LEN)?)?;
+        let msg =
+            Message::from_slice(take(&mut data, 32)?).map_err(|_| ParseError::MalformedArg)?;
+        let adaptor = match take(&mut data, 1)?[0] {
+            0 => None,
+            1 => Some(
+                PublicKey::from_slice(take(&mut data, 33)?)
+                    .map_err(|_| ParseError::MalformedArg)?,
+            ),
+            _ => return Err(ParseError::MalformedArg),
+        };
+        if !data.is_empty() {
+            return Err(ParseError::MalformedArg);
+        }
+
+        Ok(MusigSessionState {
+            agg_nonce,
+            msg,
+            adaptor,
+        })
+    }
+}
+
 /// Musig tweaking related errors.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub enum MusigSignError {
@@ -1721,9 +2171,200 @@ impl fmt::Display for MusigSignError {
     }
 }
 
+/// State of a [`Signer`] after key aggregation, ready to generate a nonce.
+#[derive(Debug)]
+pub struct KeyAgg;
+
+/// State of a [`Signer`] holding its secret nonce for a single signing session.
+#[derive(Debug)]
+pub struct NonceGenerated {
+    secnonce: MusigSecNonce,
+    pubnonce: MusigPubNonce,
+    msg: Message,
+}
+
+/// State of a [`Signer`] that has created its partial signature.
+#[derive(Debug)]
+pub struct Signed {
+    session: MusigSession,
+    partial_sig: MusigPartialSignature,
+}
+
+/// A signer in a MuSig2 signing session whose type tracks the progress of the session.
+///
+/// A signer moves from [`KeyAgg`] to [`NonceGenerated`] to [`Signed`]. Each transition consumes
+/// the signer, and the secret nonce never leaves it. Signing moves the secret nonce out of the
+/// signer, so a nonce can't be used twice without a compile error.
+///
+/// Example:
+///
+/// ```rust
+/// # # [cfg(any(test, feature = "rand-std"))] {
+/// # use secp256k1_zkp::rand::thread_rng;
+/// # use secp256k1_zkp::{KeyPair, Message, MusigAggNonce, MusigKeyAggCache, Secp256k1, Signer, XOnlyPublicKey};
+/// let secp = Secp256k1::new();
+/// let keypair1 = KeyPair::new(&secp, &mut thread_rng());
+/// let keypair2 = KeyPair::new(&secp, &mut thread_rng());
+/// let pubkeys = [XOnlyPublicKey::from_keypair(&keypair1), XOnlyPublicKey::from_keypair(&keypair2)];
+/// let key_agg_cache = MusigKeyAggCache::new(&secp, &pubkeys);
+/// let msg = Message::from_slice(&[3; 32]).unwrap();
+///
+/// let signer1 = Signer::new(keypair1, key_agg_cache)
+///     .generate_nonce(&secp, &mut thread_rng(), msg, None)
+///     .expect("non zero session id");
+/// let signer2 = Signer::new(keypair2, key_agg_cache)
+///     .generate_nonce(&secp, &mut thread_rng(), msg, None)
+///     .expect("non zero session id");
+///
+/// let aggnonce = MusigAggNonce::new(&secp, &[signer1.pub_nonce(), signer2.pub_nonce()]);
+/// let signer1 = signer1.sign(&secp, aggnonce, None).unwrap();
+/// let signer2 = signer2.sign(&secp, aggnonce, None).unwrap();
+///
+/// let sig = signer1.aggregate(&[signer1.partial_sig(), signer2.partial_sig()]);
+/// assert!(secp.verify_schnorr(&sig, &msg, &key_agg_cache.agg_pk()).is_ok());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Signer<S> {
+    keypair: KeyPair,
+    key_agg_cache: MusigKeyAggCache,
+    state: S,
+}
+
+impl<S> Signer<S> {
+    /// The key aggregation cache of the session.
+    pub fn key_agg_cache(&self) -> &MusigKeyAggCache {
+        &self.key_agg_cache
+    }
+}
+
+impl Signer<KeyAgg> {
+    /// Creates a signer for `keypair` in a session with the aggregate key of `key_agg_cache`.
+    ///
+    /// The cache must be tweaked before the signer is created if the signature is for a tweaked key.
+    pub fn new(keypair: KeyPair, key_agg_cache: MusigKeyAggCache) -> Signer<KeyAgg> {
+        Signer {
+            keypair,
+            key_agg_cache,
+            state: KeyAgg,
+        }
+    }
+
+    /// Generates the nonce pair for signing `msg`, drawing the session id from `rng`.
+    ///
+    /// See [`new_musig_nonce_pair`]. Requires compilation with "rand" feature.
+    #[cfg(any(test, feature = "rand"))]
+    pub fn generate_nonce<C: Signing, R: Rng + CryptoRng>(
+        self,
+        secp: &Secp256k1<C>,
+        rng: &mut R,
+        msg: Message,
+        extra_rand: Option<[u8; 32]>,
+    ) -> Result<Signer<NonceGenerated>, MusigNonceGenError> {
+        let mut session_id = [0u8; 32];
+        rng.fill_bytes(&mut session_id);
+        self.generate_nonce_with_session_id(secp, session_id, msg, extra_rand)
+    }
+
+    /// Generates the nonce pair for signing `msg` with a caller-provided `session_id`, see
+    /// [`new_musig_nonce_pair`].
+    ///
+    /// The `session_id` _must_ be sampled uniformly at random and never be re-used, otherwise
+    /// the secret key can leak. Prefer [`Signer::generate_nonce`] if a random number generator
+    /// is available.
+    pub fn generate_nonce_with_session_id<C: Signing>(
+        self,
+        secp: &Secp256k1<C>,
+        session_id: [u8; 32],
+        msg: Message,
+        extra_rand: Option<[u8; 32]>,
+    ) -> Result<Signer<NonceGenerated>, MusigNonceGenError> {
+        let (secnonce, pubnonce) = new_musig_nonce_pair(
+            secp,
+            session_id,
+            Some(&self.key_agg_cache),
+            Some(SecretKey::from_keypair(&self.keypair)),
+            Some(msg),
+            extra_rand,
+        )?;
+
+        Ok(Signer {
+            keypair: self.keypair,
+            key_agg_cache: self.key_agg_cache,
+            state: NonceGenerated {
+                secnonce,
+                pubnonce,
+                msg,
+            },
+        })
+    }
+}
+
+impl Signer<NonceGenerated> {
+    /// The public nonce to send to the other signers.
+    pub fn pub_nonce(&self) -> MusigPubNonce {
+        self.state.pubnonce
+    }
+
+    /// The message to be signed.
+    pub fn msg(&self) -> Message {
+        self.state.msg
+    }
+
+    /// Creates the partial signature for the session with the aggregate nonce of all signers.
+    ///
+    /// Consumes the signer together with its secret nonce. See [`MusigSession::new`] for `adaptor`.
+    pub fn sign<C: Signing>(
+        self,
+        secp: &Secp256k1<C>,
+        agg_nonce: MusigAggNonce,
+        adaptor: Option<PublicKey>,
+    ) -> Result<Signer<Signed>, MusigSignError> {
+        let Signer {
+            keypair,
+            key_agg_cache,
+            state,
+        } = self;
+        let NonceGenerated {
+            mut secnonce, msg, ..
+        } = state;
+
+        let session = MusigSession::new(secp, &key_agg_cache, agg_nonce, msg, adaptor);
+        let partial_sig = session.partial_sign(secp, &mut secnonce, &keypair, &key_agg_cache)?;
+
+        Ok(Signer {
+            keypair,
+            key_agg_cache,
+            state: Signed {
+                session,
+                partial_sig,
+            },
+        })
+    }
+}
+
+impl Signer<Signed> {
+    /// The partial signature to send to the aggregator.
+    pub fn partial_sig(&self) -> MusigPartialSignature {
+        self.state.partial_sig
+    }
+
+    /// The session the partial signature was created in, to verify partial signatures of other signers.
+    pub fn session(&self) -> &MusigSession {
+        &self.state.session
+    }
+
+    /// Aggregates the partial signatures of all signers, see [`MusigSession::partial_sig_agg`].
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn aggregate(&self, partial_sigs: &[MusigPartialSignature]) -> schnorr::Signature {
+        self.state.session.partial_sig_agg(partial_sigs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ffi::types::AlignedType;
     use rand::{thread_rng, RngCore};
     use {KeyPair, XOnlyPublicKey};
 
@@ -1759,4 +2400,342 @@ mod tests {
 
         assert_eq!(parsed_pubnonce, pubnonce);
     }
+
+    #[test]
+    fn test_musig_with_preallocated_context() {
+        let mut buf = [AlignedType::ZERO; 64];
+        let secp = Secp256k1::preallocated_new(&mut buf).unwrap();
+
+        let sec_key1 = SecretKey::from_slice(&[1; 32]).unwrap();
+        let sec_key2 = SecretKey::from_slice(&[2; 32]).unwrap();
+        let keypair1 = KeyPair::from_secret_key(&secp, sec_key1);
+        let keypair2 = KeyPair::from_secret_key(&secp, sec_key2);
+        let pub_key1 = XOnlyPublicKey::from_keypair(&keypair1);
+        let pub_key2 = XOnlyPublicKey::from_keypair(&keypair2);
+        let key_agg_cache = MusigKeyAggCache::new(&secp, &[pub_key1, pub_key2]);
+
+        let msg = Message::from_slice(&[3; 32]).unwrap();
+        let (mut secnonce1, pubnonce1) = key_agg_cache
+            .nonce_gen(&secp, [4; 32], sec_key1, msg, None)
+            .unwrap();
+        let (mut secnonce2, pubnonce2) = key_agg_cache
+            .nonce_gen(&secp, [5; 32], sec_key2, msg, None)
+            .unwrap();
+        let aggnonce = MusigAggNonce::new(&secp, &[pubnonce1, pubnonce2]);
+
+        let session = MusigSession::new(&secp, &key_agg_cache, aggnonce, msg, None);
+        let partial_sig1 = session
+            .partial_sign(&secp, &mut secnonce1, &keypair1, &key_agg_cache)
+            .unwrap();
+        let partial_sig2 = session
+            .partial_sign(&secp, &mut secnonce2, &keypair2, &key_agg_cache)
+            .unwrap();
+        assert!(session.partial_verify(&secp, &key_agg_cache, partial_sig1, pubnonce1, pub_key1));
+
+        let sig = session.partial_sig_agg(&[partial_sig1, partial_sig2]);
+        assert!(secp
+            .verify_schnorr(&sig, &msg, &key_agg_cache.agg_pk())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_signer() {
+        let secp = Secp256k1::new();
+        let keypair1 = KeyPair::from_secret_key(&secp, SecretKey::from_slice(&[1; 32]).unwrap());
+        let keypair2 = KeyPair::from_secret_key(&secp, SecretKey::from_slice(&[2; 32]).unwrap());
+        let pub_key1 = XOnlyPublicKey::from_keypair(&keypair1);
+        let pub_key2 = XOnlyPublicKey::from_keypair(&keypair2);
+        let key_agg_cache = MusigKeyAggCache::new(&secp, &[pub_key1, pub_key2]);
+        let msg = Message::from_slice(&[3; 32]).unwrap();
+
+        let signer1 = Signer::new(keypair1, key_agg_cache)
+            .generate_nonce(&secp, &mut thread_rng(), msg, None)
+            .unwrap();
+        let signer2 = Signer::new(keypair2, key_agg_cache)
+            .generate_nonce_with_session_id(&secp, [5; 32], msg, None)
+            .unwrap();
+        assert_eq!(signer1.msg(), msg);
+        let pubnonce1 = signer1.pub_nonce();
+        let aggnonce = MusigAggNonce::new(&secp, &[pubnonce1, signer2.pub_nonce()]);
+
+        let signer1 = signer1.sign(&secp, aggnonce, None).unwrap();
+        let signer2 = signer2.sign(&secp, aggnonce, None).unwrap();
+        assert!(signer2.session().partial_verify(
+            &secp,
+            signer2.key_agg_cache(),
+            signer1.partial_sig(),
+            pubnonce1,
+            pub_key1
+        ));
+
+        let sig = signer2.aggregate(&[signer1.partial_sig(), signer2.partial_sig()]);
+        assert!(secp
+            .verify_schnorr(&sig, &msg, &key_agg_cache.agg_pk())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_agg_pk_full() {
+        let secp = Secp256k1::new();
+        let pub_key1 = PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[1; 32]).unwrap());
+        let pub_key2 = PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[2; 32]).unwrap());
+        let mut key_agg_cache = MusigKeyAggCache::new(&secp, &[pub_key1.into(), pub_key2.into()]);
+
+        let agg_pk_full = key_agg_cache.agg_pk_full();
+        assert_eq!(XOnlyPublicKey::from(agg_pk_full), key_agg_cache.agg_pk());
+        assert_eq!(key_agg_cache.tweaked_pk_full(), agg_pk_full);
+
+        let tweak = SecretKey::from_slice(&[6; 32]).unwrap();
+        let tweaked_pk = key_agg_cache.pubkey_ec_tweak_add(&secp, tweak).unwrap();
+        let mut expected = agg_pk_full;
+        expected.add_exp_assign(&secp, tweak.as_ref()).unwrap();
+        assert_eq!(tweaked_pk, expected);
+        assert_eq!(key_agg_cache.tweaked_pk_full(), tweaked_pk);
+
+        key_agg_cache.pubkey_xonly_tweak_add(&secp, tweak).unwrap();
+        assert_eq!(key_agg_cache.agg_pk_full(), agg_pk_full);
+    }
+
+    const PUBNONCE_HEX: &str = "0388ea56157451d2e61fa21c22fa088506b052658570c56715decc906a6d136d7802e458a2883ce14fd2515d11198a92c784acdbdb604104d0c548dd510e3c0edb27000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    const AGGNONCE_HEX: &str = "02352d309ca2104aa93f3defc9cfe67e61a3a92967d665a0330c84baedd77f0b4d02a320abe2079b2f6398ab470493f149b7cdea5e1bb99ec359f536a4d6ab098bbd000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    const PARTIAL_SIG_HEX: &str =
+        "21b01f73fe028c44bf0918ab6563c8db256cd565f41870ac0d0a9759fa75b722";
+
+    #[test]
+    fn test_musig_hex_encoding() {
+        let pubnonce = PUBNONCE_HEX.parse::<MusigPubNonce>().unwrap();
+        assert_eq!(pubnonce.to_string(), PUBNONCE_HEX);
+        let aggnonce = AGGNONCE_HEX.parse::<MusigAggNonce>().unwrap();
+        assert_eq!(aggnonce.to_string(), AGGNONCE_HEX);
+        let partial_sig = PARTIAL_SIG_HEX.parse::<MusigPartialSignature>().unwrap();
+        assert_eq!(partial_sig.to_string(), PARTIAL_SIG_HEX);
+
+        assert_eq!(
+            "zz".parse::<MusigPartialSignature>(),
+            Err(ParseError::MalformedArg)
+        );
+        assert_eq!(
+            "0000".parse::<MusigPartialSignature>(),
+            Err(ParseError::ArgLenMismatch {
+                expected: 32,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_musig_persistence() {
+        let secp = Secp256k1::new();
+        let sec_key1 = SecretKey::from_slice(&[1; 32]).unwrap();
+        let sec_key2 = SecretKey::from_slice(&[2; 32]).unwrap();
+        let keypair1 = KeyPair::from_secret_key(&secp, sec_key1);
+        let keypair2 = KeyPair::from_secret_key(&secp, sec_key2);
+        let pub_key1 = XOnlyPublicKey::from_keypair(&keypair1);
+        let pub_key2 = XOnlyPublicKey::from_keypair(&keypair2);
+
+        let mut cache_state = MusigKeyAggCacheState::new(&secp, &[pub_key1, pub_key2]);
+        assert_eq!(
+            cache_state.key_agg_cache(),
+            MusigKeyAggCache::new(&secp, &[pub_key1, pub_key2])
+        );
+        let tweak = SecretKey::from_slice(&[6; 32]).unwrap();
+        cache_state.pubkey_ec_tweak_add(&secp, tweak).unwrap();
+        let tweaked_pk = cache_state.pubkey_xonly_tweak_add(&secp, tweak).unwrap();
+        assert_eq!(cache_state.pubkeys(), &[pub_key1, pub_key2]);
+        assert_eq!(
+            cache_state.tweaks(),
+            &[MusigTweak::Ec(tweak), MusigTweak::XOnly(tweak)]
+        );
+
+        let ser_cache = cache_state.serialize();
+        assert_eq!(ser_cache.len(), 1 + 4 + 2 * 32 + 4 + 2 * 33);
+        assert_eq!(
+            MusigKeyAggCacheState::from_slice(&ser_cache),
+            Ok(cache_state.clone())
+        );
+        assert_eq!(
+            cache_state.to_string().parse::<MusigKeyAggCacheState>(),
+            Ok(cache_state.clone())
+        );
+
+        let key_agg_cache = cache_state.key_agg_cache();
+        let msg = Message::from_slice(&[3; 32]).unwrap();
+        let (mut secnonce1, pubnonce1) = key_agg_cache
+            .nonce_gen(&secp, [4; 32], sec_key1, msg, None)
+            .unwrap();
+        let (mut secnonce2, pubnonce2) = key_agg_cache
+            .nonce_gen(&secp, [5; 32], sec_key2, msg, None)
+            .unwrap();
+        let session_state = MusigSessionState {
+            agg_nonce: MusigAggNonce::new(&secp, &[pubnonce1, pubnonce2]),
+            msg,
+            adaptor: None,
+        };
+        let adaptor_state = MusigSessionState {
+            adaptor: Some(PublicKey::from_secret_key(&secp, &sec_key2)),
+            ..session_state
+        };
+        assert_eq!(
+            MusigSessionState::from_slice(&adaptor_state.serialize()),
+            Ok(adaptor_state)
+        );
+        assert_eq!(
+            adaptor_state.to_string().parse::<MusigSessionState>(),
+            Ok(adaptor_state)
+        );
+
+        // Sign with a session and cache that were persisted in between the rounds.
+        let key_agg_cache = MusigKeyAggCacheState::from_slice(&ser_cache)
+            .unwrap()
+            .key_agg_cache();
+        let session = MusigSessionState::from_slice(&session_state.serialize())
+            .unwrap()
+            .session(&secp, &key_agg_cache);
+        assert_eq!(session, session_state.session(&secp, &key_agg_cache));
+        let partial_sig1 = session
+            .partial_sign(&secp, &mut secnonce1, &keypair1, &key_agg_cache)
+            .unwrap();
+        let partial_sig2 = session
+            .partial_sign(&secp, &mut secnonce2, &keypair2, &key_agg_cache)
+            .unwrap();
+        assert!(session.partial_verify(&secp, &key_agg_cache, partial_sig1, pubnonce1, pub_key1));
+        let sig = session.partial_sig_agg(&[partial_sig1, partial_sig2]);
+        // The tweaked key is returned with the y coordinate of the full point, normalize it.
+        let tweaked_pk = XOnlyPublicKey::from_slice(&tweaked_pk.serialize()).unwrap();
+        assert!(secp.verify_schnorr(&sig, &msg, &tweaked_pk).is_ok());
+
+        // Corrupted data is rejected.
+        let mut data = ser_cache.clone();
+        data[0] = 2;
+        assert_eq!(
+            MusigKeyAggCacheState::from_slice(&data),
+            Err(ParseError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            MusigKeyAggCacheState::from_slice(&ser_cache[..ser_cache.len() - 1]),
+            Err(ParseError::MalformedArg)
+        );
+        let mut data = ser_cache.clone();
+        data.push(0);
+        assert_eq!(
+            MusigKeyAggCacheState::from_slice(&data),
+            Err(ParseError::MalformedArg)
+        );
+        // Unknown tweak kind
+        let mut data = ser_cache.clone();
+        data[1 + 4 + 2 * 32 + 4] = 2;
+        assert_eq!(
+            MusigKeyAggCacheState::from_slice(&data),
+            Err(ParseError::MalformedArg)
+        );
+        // No public keys
+        assert_eq!(
+            MusigKeyAggCacheState::from_slice(&[1, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(ParseError::MalformedArg)
+        );
+        // Unknown adaptor flag
+        let mut data = session_state.serialize();
+        data[1 + ffi::MUSIG_AGGNONCE_LEN + 32] = 2;
+        assert_eq!(
+            MusigSessionState::from_slice(&data),
+            Err(ParseError::MalformedArg)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_musig_serde() {
+        use serde_test::Configure;
+        use serde_test::{assert_tokens, Token};
+
+        let pubnonce = PUBNONCE_HEX.parse::<MusigPubNonce>().unwrap();
+        assert_tokens(&pubnonce.readable(), &[Token::Str(PUBNONCE_HEX)]);
+        assert_tokens(
+            &pubnonce.compact(),
+            &[Token::Bytes(&[
+                3, 136, 234, 86, 21, 116, 81, 210, 230, 31, 162, 28, 34, 250, 8, 133, 6, 176, 82,
+                101, 133, 112, 197, 103, 21, 222, 204, 144, 106, 109, 19, 109, 120, 2, 228, 88,
+                162, 136, 60, 225, 79, 210, 81, 93, 17, 25, 138, 146, 199, 132, 172, 219, 219, 96,
+                65, 4, 208, 197, 72, 221, 81, 14, 60, 14, 219, 39, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ])],
+        );
+
+        let aggnonce = AGGNONCE_HEX.parse::<MusigAggNonce>().unwrap();
+        assert_tokens(&aggnonce.readable(), &[Token::Str(AGGNONCE_HEX)]);
+        assert_tokens(
+            &aggnonce.compact(),
+            &[Token::Bytes(&[
+                2, 53, 45, 48, 156, 162, 16, 74, 169, 63, 61, 239, 201, 207, 230, 126, 97, 163,
+                169, 41, 103, 214, 101, 160, 51, 12, 132, 186, 237, 215, 127, 11, 77, 2, 163, 32,
+                171, 226, 7, 155, 47, 99, 152, 171, 71, 4, 147, 241, 73, 183, 205, 234, 94, 27,
+                185, 158, 195, 89, 245, 54, 164, 214, 171, 9, 139, 189, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0,
+            ])],
+        );
+
+        let partial_sig = PARTIAL_SIG_HEX.parse::<MusigPartialSignature>().unwrap();
+        assert_tokens(&partial_sig.readable(), &[Token::Str(PARTIAL_SIG_HEX)]);
+        assert_tokens(
+            &partial_sig.compact(),
+            &[Token::Bytes(&[
+                33, 176, 31, 115, 254, 2, 140, 68, 191, 9, 24, 171, 101, 99, 200, 219, 37, 108,
+                213, 101, 244, 24, 112, 172, 13, 10, 151, 89, 250, 117, 183, 34,
+            ])],
+        );
+
+        let secp = Secp256k1::new();
+        let pub_key1 = XOnlyPublicKey::from_keypair(&KeyPair::from_secret_key(
+            &secp,
+            SecretKey::from_slice(&[1; 32]).unwrap(),
+        ));
+        let pub_key2 = XOnlyPublicKey::from_keypair(&KeyPair::from_secret_key(
+            &secp,
+            SecretKey::from_slice(&[2; 32]).unwrap(),
+        ));
+        let mut cache_state = MusigKeyAggCacheState::new(&secp, &[pub_key1, pub_key2]);
+        cache_state
+            .pubkey_xonly_tweak_add(&secp, SecretKey::from_slice(&[6; 32]).unwrap())
+            .unwrap();
+        assert_tokens(
+            &cache_state.clone().readable(),
+            &[Token::Str("01020000001b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f4d4b6cd1361032ca9bd2aeb9d900aa4d45d9ead80ac9423374c451a7254d076601000000010606060606060606060606060606060606060606060606060606060606060606")],
+        );
+        assert_tokens(
+            &cache_state.compact(),
+            &[Token::Bytes(&[
+                1, 2, 0, 0, 0, 27, 132, 197, 86, 123, 18, 100, 64, 153, 93, 62, 213, 170, 186, 5,
+                101, 215, 30, 24, 52, 96, 72, 25, 255, 156, 23, 245, 233, 213, 221, 7, 143, 77, 75,
+                108, 209, 54, 16, 50, 202, 155, 210, 174, 185, 217, 0, 170, 77, 69, 217, 234, 216,
+                10, 201, 66, 51, 116, 196, 81, 167, 37, 77, 7, 102, 1, 0, 0, 0, 1, 6, 6, 6, 6, 6,
+                6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6,
+            ])],
+        );
+
+        let session_state = MusigSessionState {
+            agg_nonce: aggnonce,
+            msg: Message::from_slice(&[3; 32]).unwrap(),
+            adaptor: None,
+        };
+        assert_tokens(
+            &session_state.readable(),
+            &[Token::Str("0102352d309ca2104aa93f3defc9cfe67e61a3a92967d665a0330c84baedd77f0b4d02a320abe2079b2f6398ab470493f149b7cdea5e1bb99ec359f536a4d6ab098bbd000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000030303030303030303030303030303030303030303030303030303030303030300")],
+        );
+        assert_tokens(
+            &session_state.compact(),
+            &[Token::Bytes(&[
+                1, 2, 53, 45, 48, 156, 162, 16, 74, 169, 63, 61, 239, 201, 207, 230, 126, 97, 163,
+                169, 41, 103, 214, 101, 160, 51, 12, 132, 186, 237, 215, 127, 11, 77, 2, 163, 32,
+                171, 226, 7, 155, 47, 99, 152, 171, 71, 4, 147, 241, 73, 183, 205, 234, 94, 27,
+                185, 158, 195, 89, 245, 54, 164, 214, 171, 9, 139, 189, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3,
+                3, 3, 3, 3, 3, 0,
+            ])],
+        );
+    }
 }
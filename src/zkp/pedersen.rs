@@ -1,4 +1,8 @@
-use core::{fmt, slice, str};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use alloc::vec::Vec;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::slice;
+use core::{fmt, str};
 use ffi;
 use {from_hex, Error, Generator, Secp256k1, Signing, Tweak, ZERO_TWEAK};
 
@@ -117,6 +121,7 @@ impl CommitmentSecrets {
 }
 
 /// Compute a blinding factor such that the sum of all blinding factors in both sets is equal.
+#[cfg(any(feature = "std", feature = "alloc"))]
 pub fn compute_adaptive_blinding_factor<C: Signing>(
     secp: &Secp256k1<C>,
     value: u64,
@@ -162,6 +167,7 @@ pub fn compute_adaptive_blinding_factor<C: Signing>(
 }
 
 /// Verifies that the sum of the committed values within the commitments of both sets is equal.
+#[cfg(any(feature = "std", feature = "alloc"))]
 #[must_use]
 pub fn verify_commitments_sum_to_equal<C: Signing>(
     secp: &Secp256k1<C>,
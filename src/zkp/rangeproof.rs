@@ -1,22 +1,50 @@
+#[cfg(any(feature = "std", feature = "alloc"))]
+use alloc::boxed::Box;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use alloc::vec::Vec;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::cmp;
+use core::ops::Range;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::str;
+#[cfg(any(feature = "std", feature = "alloc"))]
 use ffi::RANGEPROOF_MAX_LENGTH;
+#[cfg(any(feature = "std", feature = "alloc"))]
 use from_hex;
-use std::ops::Range;
-use std::str;
 use Error;
 use Generator;
 use PedersenCommitment;
 use Verification;
-use {ffi, Secp256k1, SecretKey, Signing, Tweak};
+use {ffi, Secp256k1, Tag, Tweak};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use {SecretKey, Signing};
 
 /// Represents a range proof.
-///
-/// TODO: Store rangeproof info
+#[cfg(any(feature = "std", feature = "alloc"))]
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
 pub struct RangeProof {
     inner: ffi::RangeProof,
+    info: RangeProofInfo,
+}
+
+/// Public parameters of a range proof which can be extracted without verifying it.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct RangeProofInfo {
+    /// The exponent used in the proof, -1 if the value is revealed.
+    pub exp: i32,
+    /// The number of bits of the value that are kept private.
+    pub mantissa_bits: u8,
+    /// The smallest value the committed value can take on.
+    pub min_value: u64,
+    /// The largest value the committed value can take on.
+    pub max_value: u64,
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl RangeProof {
+    /// The maximum length of the message that can be recovered by rewinding a range proof.
+    pub const MAX_MESSAGE_LEN: usize = 4096;
+
     /// Serialize to bytes.
     pub fn serialize(&self) -> Vec<u8> {
         self.inner.to_bytes()
@@ -49,9 +77,22 @@ impl RangeProof {
 
         Ok(RangeProof {
             inner: ffi::RangeProof::new(bytes),
+            info: RangeProofInfo {
+                exp,
+                mantissa_bits: mantissa as u8,
+                min_value,
+                max_value,
+            },
         })
     }
 
+    /// Returns the public parameters of the proof.
+    ///
+    /// These are not verified, use [`RangeProof::verify`] to check that the committed value is actually within the range.
+    pub fn info(&self) -> RangeProofInfo {
+        self.info
+    }
+
     /// Get length.
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -63,6 +104,8 @@ impl RangeProof {
     }
 
     /// Prove that `commitment` hides a value within a range, with the lower bound set to `min_value`.
+    ///
+    /// See [`RangeProofBuilder`] for a less error-prone way to create range proofs.
     pub fn new<C: Signing>(
         secp: &Secp256k1<C>,
         min_value: u64,
@@ -103,14 +146,13 @@ impl RangeProof {
             return Err(Error::CannotMakeRangeProof);
         }
 
-        Ok(RangeProof {
-            inner: ffi::RangeProof::new(&proof[..proof_length]),
-        })
+        RangeProof::from_slice(&proof[..proof_length])
     }
 
     /// Verify that the committed value is within a range.
     ///
     /// If the verification is successful, return the actual range of possible values.
+    /// Verification doesn't allocate.
     pub fn verify<C: Verification>(
         &self,
         secp: &Secp256k1<C>,
@@ -118,34 +160,20 @@ impl RangeProof {
         additional_commitment: &[u8],
         additional_generator: Generator,
     ) -> Result<Range<u64>, Error> {
-        let mut min_value = 0u64;
-        let mut max_value = 0u64;
-
-        let ret = unsafe {
-            ffi::secp256k1_rangeproof_verify(
-                *secp.ctx(),
-                &mut min_value,
-                &mut max_value,
-                commitment.as_inner(),
-                self.inner.as_ptr(),
-                self.inner.len(),
-                additional_commitment.as_ptr(),
-                additional_commitment.len(),
-                additional_generator.as_inner(),
-            )
-        };
-
-        if ret == 0 {
-            return Err(Error::InvalidRangeProof);
-        }
-
-        Ok(Range {
-            start: min_value,
-            end: max_value + 1,
-        })
+        verify_range_proof(
+            secp,
+            self.inner.as_slice(),
+            commitment,
+            additional_commitment,
+            additional_generator,
+        )
     }
 
     /// Verify a range proof proof and rewind the proof to recover information sent by its author.
+    ///
+    /// The message is first recovered into a [`RangeProof::MAX_MESSAGE_LEN`] (4096) byte buffer on
+    /// the stack and then copied to the heap. Where stack space is tight, use
+    /// [`RangeProof::rewind_into`] with a buffer of your own instead.
     pub fn rewind<C: Verification>(
         &self,
         secp: &Secp256k1<C>,
@@ -154,13 +182,46 @@ impl RangeProof {
         additional_commitment: &[u8],
         additional_generator: Generator,
     ) -> Result<(Opening, Range<u64>), Error> {
+        let mut message = [0u8; RangeProof::MAX_MESSAGE_LEN];
+
+        let (opening, range) = self.rewind_into(
+            secp,
+            commitment,
+            sk,
+            additional_commitment,
+            additional_generator,
+            &mut message,
+        )?;
+
+        let opening = Opening {
+            value: opening.value,
+            blinding_factor: opening.blinding_factor,
+            message: opening.message.into(),
+        };
+
+        Ok((opening, range))
+    }
+
+    /// Same as [`RangeProof::rewind`], but writes the embedded message to the caller-provided
+    /// `message` buffer instead of allocating.
+    ///
+    /// A buffer shorter than [`RangeProof::MAX_MESSAGE_LEN`] receives a truncated message.
+    /// The buffer may live anywhere; no message buffer is placed on the stack.
+    pub fn rewind_into<'m, C: Verification>(
+        &self,
+        secp: &Secp256k1<C>,
+        commitment: PedersenCommitment,
+        sk: SecretKey,
+        additional_commitment: &[u8],
+        additional_generator: Generator,
+        message: &'m mut [u8],
+    ) -> Result<(OpeningRef<'m>, Range<u64>), Error> {
         let mut min_value = 0u64;
         let mut max_value = 0u64;
 
         let mut blinding_factor = [0u8; 32];
         let mut value = 0u64;
-        let mut message = [0u8; 4096];
-        let mut message_length = 4096usize;
+        let mut message_length = message.len();
 
         let ret = unsafe {
             ffi::secp256k1_rangeproof_rewind(
@@ -185,10 +246,10 @@ impl RangeProof {
             return Err(Error::InvalidRangeProof);
         }
 
-        let opening = Opening {
+        let opening = OpeningRef {
             value,
             blinding_factor: Tweak::from_slice(&blinding_factor)?,
-            message: message[..message_length].into(),
+            message: &message[..message_length],
         };
 
         let range = Range {
@@ -200,7 +261,329 @@ impl RangeProof {
     }
 }
 
-#[cfg(feature = "bitcoin_hashes")]
+/// Verify that the committed value of a serialized range proof is within a range.
+///
+/// Same as [`RangeProof::verify`], but checks the proof in place without parsing it into a [`RangeProof`],
+/// so it is available without an allocator.
+pub fn verify_range_proof<C: Verification>(
+    secp: &Secp256k1<C>,
+    proof: &[u8],
+    commitment: PedersenCommitment,
+    additional_commitment: &[u8],
+    additional_generator: Generator,
+) -> Result<Range<u64>, Error> {
+    let mut min_value = 0u64;
+    let mut max_value = 0u64;
+
+    let ret = unsafe {
+        ffi::secp256k1_rangeproof_verify(
+            *secp.ctx(),
+            &mut min_value,
+            &mut max_value,
+            commitment.as_inner(),
+            proof.as_ptr(),
+            proof.len(),
+            additional_commitment.as_ptr(),
+            additional_commitment.len(),
+            additional_generator.as_inner(),
+        )
+    };
+
+    if ret == 0 {
+        return Err(Error::InvalidRangeProof);
+    }
+
+    Ok(Range {
+        start: min_value,
+        end: max_value + 1,
+    })
+}
+
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "bitcoin_hashes"))]
+mod with_hashes {
+    use super::*;
+    use ecdh::SharedSecret;
+    use hashes::{sha256, Hash};
+    use PublicKey;
+
+    impl RangeProof {
+        /// Prove that `commitment` hides a value within a range, deriving the nonce the way Elements does for
+        /// transaction outputs.
+        ///
+        /// The nonce is `SHA256(ECDH(ephemeral_sk, blinding_pk))`, where `blinding_pk` is the receiver's blinding
+        /// public key. The public key of `ephemeral_sk` has to be placed in the output's nonce field so that the
+        /// receiver can rewind the proof with [`RangeProof::rewind_with_blinding_key`].
+        ///
+        /// All other parameters are the same as for [`RangeProof::new`].
+        #[allow(clippy::too_many_arguments)]
+        pub fn new_for_output<C: Signing>(
+            secp: &Secp256k1<C>,
+            min_value: u64,
+            commitment: PedersenCommitment,
+            value: u64,
+            commitment_blinding: Tweak,
+            message: &[u8],
+            additional_commitment: &[u8],
+            ephemeral_sk: SecretKey,
+            blinding_pk: PublicKey,
+            exp: i32,
+            min_bits: u8,
+            additional_generator: Generator,
+        ) -> Result<RangeProof, Error> {
+            RangeProof::new(
+                secp,
+                min_value,
+                commitment,
+                value,
+                commitment_blinding,
+                message,
+                additional_commitment,
+                output_nonce(&blinding_pk, &ephemeral_sk)?,
+                exp,
+                min_bits,
+                additional_generator,
+            )
+        }
+
+        /// Verify a range proof created with [`RangeProof::new_for_output`] and rewind it using the receiver's
+        /// blinding key and the ephemeral public key found in the output's nonce field.
+        ///
+        /// Like [`RangeProof::rewind`], this recovers the message via a 4096-byte stack buffer.
+        pub fn rewind_with_blinding_key<C: Verification>(
+            &self,
+            secp: &Secp256k1<C>,
+            commitment: PedersenCommitment,
+            blinding_key: SecretKey,
+            ephemeral_pk: PublicKey,
+            additional_commitment: &[u8],
+            additional_generator: Generator,
+        ) -> Result<(Opening, Range<u64>), Error> {
+            self.rewind(
+                secp,
+                commitment,
+                output_nonce(&ephemeral_pk, &blinding_key)?,
+                additional_commitment,
+                additional_generator,
+            )
+        }
+    }
+
+    /// Computes the Elements range proof nonce `SHA256(ECDH(sk, pk))`.
+    ///
+    /// The ECDH function of libsecp256k1 already hashes the compressed shared point with SHA256.
+    fn output_nonce(pk: &PublicKey, sk: &SecretKey) -> Result<SecretKey, Error> {
+        let shared_secret = SharedSecret::new(pk, sk);
+        let nonce = sha256::Hash::hash(&shared_secret.secret_bytes());
+
+        Ok(SecretKey::from_slice(&nonce[..])?)
+    }
+}
+
+/// Creates a [`RangeProof`] from named parameters.
+///
+/// Start from one of the presets [`RangeProofBuilder::confidential`] or [`RangeProofBuilder::exact_value`]
+/// and adjust the parameters as needed. All parameters are validated before calling into `libsecp256k1-zkp`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Debug, Clone)]
+pub struct RangeProofBuilder<'a> {
+    value: u64,
+    commitment: PedersenCommitment,
+    commitment_blinding: Tweak,
+    additional_generator: Generator,
+    nonce: SecretKey,
+    min_value: u64,
+    exp: i32,
+    min_bits: u8,
+    message: &'a [u8],
+    additional_commitment: &'a [u8],
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> RangeProofBuilder<'a> {
+    /// The largest supported base-10 exponent.
+    pub const MAX_EXP: i32 = 18;
+    /// The largest supported number of private bits.
+    pub const MAX_MIN_BITS: u8 = 64;
+
+    /// Preset for a proof that keeps the value confidential, using exponent 0 and at least 52 private bits
+    /// with a minimum value of 0.
+    ///
+    /// `commitment` must be a commitment to `value` using `commitment_blinding` and `additional_generator`.
+    /// The `nonce` is required to rewind the proof later on.
+    pub fn confidential(
+        value: u64,
+        commitment: PedersenCommitment,
+        commitment_blinding: Tweak,
+        additional_generator: Generator,
+        nonce: SecretKey,
+    ) -> RangeProofBuilder<'a> {
+        RangeProofBuilder {
+            value,
+            commitment,
+            commitment_blinding,
+            additional_generator,
+            nonce,
+            min_value: 0,
+            exp: 0,
+            min_bits: 52,
+            message: &[],
+            additional_commitment: &[],
+        }
+    }
+
+    /// Preset for a proof that reveals the exact value, i.e. proves that `commitment` is a blinding of `value`.
+    ///
+    /// Such a proof can't carry a message, setting a non-zero [`RangeProofBuilder::min_bits`], a
+    /// [`RangeProofBuilder::min_value`] other than `value` or a message will fail.
+    pub fn exact_value(
+        value: u64,
+        commitment: PedersenCommitment,
+        commitment_blinding: Tweak,
+        additional_generator: Generator,
+        nonce: SecretKey,
+    ) -> RangeProofBuilder<'a> {
+        RangeProofBuilder {
+            value,
+            commitment,
+            commitment_blinding,
+            additional_generator,
+            nonce,
+            min_value: value,
+            exp: -1,
+            min_bits: 0,
+            message: &[],
+            additional_commitment: &[],
+        }
+    }
+
+    /// Sets the publicly known lower bound of the value.
+    pub fn min_value(mut self, min_value: u64) -> RangeProofBuilder<'a> {
+        self.min_value = min_value;
+        self
+    }
+
+    /// Sets the base-10 exponent. Digits below it are made public but the proof gets smaller.
+    ///
+    /// Must be between -1 and [`RangeProofBuilder::MAX_EXP`], where -1 reveals the exact value.
+    pub fn exp(mut self, exp: i32) -> RangeProofBuilder<'a> {
+        self.exp = exp;
+        self
+    }
+
+    /// Sets the minimum number of bits of the value to keep private.
+    ///
+    /// Must be at most [`RangeProofBuilder::MAX_MIN_BITS`].
+    pub fn min_bits(mut self, min_bits: u8) -> RangeProofBuilder<'a> {
+        self.min_bits = min_bits;
+        self
+    }
+
+    /// Sets the message to embed in the proof, which can be recovered by rewinding it.
+    pub fn message(mut self, message: &'a [u8]) -> RangeProofBuilder<'a> {
+        self.message = message;
+        self
+    }
+
+    /// Sets additional data to be covered by the proof.
+    pub fn additional_commitment(
+        mut self,
+        additional_commitment: &'a [u8],
+    ) -> RangeProofBuilder<'a> {
+        self.additional_commitment = additional_commitment;
+        self
+    }
+
+    /// Creates the range proof.
+    pub fn build<C: Signing>(&self, secp: &Secp256k1<C>) -> Result<RangeProof, Error> {
+        if self.exp < -1 || self.exp > Self::MAX_EXP {
+            return Err(Error::InvalidRangeProofExp);
+        }
+        if self.min_bits > Self::MAX_MIN_BITS {
+            return Err(Error::InvalidRangeProofMinBits);
+        }
+        if self.min_value > self.value {
+            return Err(Error::RangeProofMinValueExceedsValue);
+        }
+
+        if self.exp == -1 {
+            if self.min_bits != 0 || self.min_value != self.value || !self.message.is_empty() {
+                return Err(Error::InvalidExactValueRangeProof);
+            }
+        } else {
+            const MAX_SIGNED: u64 = 0x7fff_ffff_ffff_ffff;
+            // The proven range must not exceed 2^64.
+            if (self.min_value != 0 && self.value > MAX_SIGNED)
+                || (self.value != 0 && self.min_value >= MAX_SIGNED)
+            {
+                return Err(Error::RangeProofValueOutOfBounds);
+            }
+            if self.message.len() > self.message_capacity() {
+                return Err(Error::RangeProofMessageTooLong);
+            }
+        }
+
+        RangeProof::new(
+            secp,
+            self.min_value,
+            self.commitment,
+            self.value,
+            self.commitment_blinding,
+            self.message,
+            self.additional_commitment,
+            self.nonce,
+            self.exp,
+            self.min_bits,
+            self.additional_generator,
+        )
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> RangeProofBuilder<'a> {
+    /// The number of message bytes a proof with these parameters can embed.
+    ///
+    /// Mirrors `secp256k1_range_proveparams` in `libsecp256k1-zkp`: the value is proven in radix-4
+    /// digits, each but the last of which can carry 128 bytes of the message. Assumes the exponent
+    /// and bounds have been validated already.
+    fn message_capacity(&self) -> usize {
+        const MAX_SIGNED: u64 = 0x7fff_ffff_ffff_ffff;
+        const MAX: u64 = 0xffff_ffff_ffff_ffff;
+
+        let mut exp = self.exp;
+        let max_bits = if self.min_value != 0 {
+            self.min_value.leading_zeros() as u8
+        } else {
+            64
+        };
+        let min_bits = cmp::min(self.min_bits, max_bits);
+        if min_bits > 61 || self.value > MAX_SIGNED {
+            exp = 0;
+        }
+
+        let mut v = self.value - self.min_value;
+        let mut v2 = if min_bits != 0 {
+            MAX >> (64 - min_bits)
+        } else {
+            0
+        };
+        let mut i = 0;
+        while i < exp && v2 <= MAX / 10 {
+            v /= 10;
+            v2 *= 10;
+            i += 1;
+        }
+
+        let mantissa = if v != 0 {
+            64 - v.leading_zeros() as u8
+        } else {
+            1
+        };
+        let rings = (cmp::max(mantissa, min_bits) as usize + 1) >> 1;
+        128 * (rings - 1)
+    }
+}
+
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "bitcoin_hashes"))]
 impl ::core::fmt::Display for RangeProof {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
         use hashes::hex::format_hex;
@@ -209,6 +592,7 @@ impl ::core::fmt::Display for RangeProof {
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl str::FromStr for RangeProof {
     type Err = Error;
     fn from_str(s: &str) -> Result<RangeProof, Error> {
@@ -220,7 +604,11 @@ impl str::FromStr for RangeProof {
     }
 }
 
-#[cfg(all(feature = "serde", feature = "bitcoin_hashes"))]
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    feature = "serde",
+    feature = "bitcoin_hashes"
+))]
 impl ::serde::Serialize for RangeProof {
     fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
         if s.is_human_readable() {
@@ -231,7 +619,11 @@ impl ::serde::Serialize for RangeProof {
     }
 }
 
-#[cfg(all(feature = "serde", feature = "bitcoin_hashes"))]
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    feature = "serde",
+    feature = "bitcoin_hashes"
+))]
 impl<'de> ::serde::Deserialize<'de> for RangeProof {
     fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<RangeProof, D::Error> {
         use serde_util;
@@ -250,6 +642,7 @@ impl<'de> ::serde::Deserialize<'de> for RangeProof {
 /// The result of rewinding a range proof.
 ///
 /// Rewinding a range proof reveals ("opens") the stored information and allows us to access information the prover embedded in the proof.
+#[cfg(any(feature = "std", feature = "alloc"))]
 pub struct Opening {
     /// The value that the prover originally committed to in the Pedersen commitment.
     pub value: u64,
@@ -259,12 +652,104 @@ pub struct Opening {
     pub message: Box<[u8]>,
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Opening {
+    /// Parses the embedded message as the asset and asset blinding factor of an Elements transaction output.
+    ///
+    /// The message may be followed by zero padding, as returned by [`RangeProof::rewind`].
+    pub fn asset_message(&self) -> Result<RangeProofMessage, Error> {
+        RangeProofMessage::from_padded_slice(&self.message)
+    }
+}
+
+/// The result of rewinding a range proof into a caller-provided buffer, see [`RangeProof::rewind_into`].
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub struct OpeningRef<'m> {
+    /// The value that the prover originally committed to in the Pedersen commitment.
+    pub value: u64,
+    /// The blinding factor that was used to create the Pedersen commitment of above value.
+    pub blinding_factor: Tweak,
+    /// The message that was embedded by the prover.
+    pub message: &'m [u8],
+}
+
+impl<'m> OpeningRef<'m> {
+    /// Parses the embedded message as the asset and asset blinding factor of an Elements transaction output.
+    ///
+    /// See [`Opening::asset_message`].
+    pub fn asset_message(&self) -> Result<RangeProofMessage, Error> {
+        RangeProofMessage::from_padded_slice(self.message)
+    }
+}
+
+/// The message embedded in the range proof of an Elements transaction output.
+///
+/// It consists of the 32-byte asset id followed by the 32-byte asset blinding factor, which allows the receiver
+/// to unblind the asset when rewinding the proof.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct RangeProofMessage {
+    /// The asset id of the output.
+    pub asset: Tag,
+    /// The blinding factor of the output's asset generator.
+    pub asset_blinding_factor: Tweak,
+}
+
+impl RangeProofMessage {
+    /// The length of a serialized message.
+    pub const LEN: usize = 64;
+
+    /// Creates a new message from the asset id and its blinding factor.
+    pub fn new(asset: Tag, asset_blinding_factor: Tweak) -> RangeProofMessage {
+        RangeProofMessage {
+            asset,
+            asset_blinding_factor,
+        }
+    }
+
+    /// Parses a message from exactly [`RangeProofMessage::LEN`] bytes.
+    pub fn from_slice(bytes: &[u8]) -> Result<RangeProofMessage, Error> {
+        if bytes.len() != RangeProofMessage::LEN {
+            return Err(Error::InvalidRangeProofMessage);
+        }
+
+        let mut asset = [0u8; 32];
+        asset.copy_from_slice(&bytes[..32]);
+        let asset_blinding_factor =
+            Tweak::from_slice(&bytes[32..]).map_err(|_| Error::InvalidRangeProofMessage)?;
+
+        Ok(RangeProofMessage {
+            asset: Tag::from(asset),
+            asset_blinding_factor,
+        })
+    }
+
+    /// Parses a message that may be followed by zero padding, as recovered by rewinding a range proof.
+    fn from_padded_slice(bytes: &[u8]) -> Result<RangeProofMessage, Error> {
+        if bytes.len() < RangeProofMessage::LEN
+            || bytes[RangeProofMessage::LEN..].iter().any(|b| *b != 0)
+        {
+            return Err(Error::InvalidRangeProofMessage);
+        }
+
+        RangeProofMessage::from_slice(&bytes[..RangeProofMessage::LEN])
+    }
+
+    /// Serializes the message, to be passed as the message when creating a range proof.
+    pub fn serialize(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(self.asset.as_ref());
+        bytes[32..].copy_from_slice(self.asset_blinding_factor.as_ref());
+        bytes
+    }
+}
+
 #[cfg(all(test, feature = "global-context"))] // use global context for convenience
 mod tests {
     use super::*;
+    use ffi::types::AlignedType;
     use rand::thread_rng;
+    use CommitmentSecrets;
     use SECP256K1;
-    use {CommitmentSecrets, Tag};
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test as test;
@@ -306,6 +791,439 @@ mod tests {
                 additional_generator,
             )
             .unwrap();
+
+        let bytes = proof.serialize();
+        assert_eq!(
+            verify_range_proof(
+                SECP256K1,
+                &bytes,
+                commitment,
+                additional_commitment,
+                additional_generator,
+            ),
+            proof.verify(
+                SECP256K1,
+                commitment,
+                additional_commitment,
+                additional_generator,
+            )
+        );
+        assert_eq!(
+            verify_range_proof(
+                SECP256K1,
+                &bytes[1..],
+                commitment,
+                additional_commitment,
+                additional_generator,
+            ),
+            Err(Error::InvalidRangeProof)
+        );
+    }
+
+    #[test]
+    fn range_proof_with_preallocated_context() {
+        let mut buf = [AlignedType::ZERO; 64];
+        let secp = Secp256k1::preallocated_new(&mut buf).unwrap();
+
+        let value = 1_000;
+        let commitment_secrets = CommitmentSecrets::random(value);
+        let tag = Tag::random();
+        let additional_generator =
+            Generator::new_blinded(&secp, tag, commitment_secrets.generator_blinding_factor);
+        let commitment = PedersenCommitment::new(
+            &secp,
+            value,
+            commitment_secrets.value_blinding_factor,
+            additional_generator,
+        );
+        let sk = SecretKey::new(&mut thread_rng());
+
+        let proof = RangeProofBuilder::confidential(
+            value,
+            commitment,
+            commitment_secrets.value_blinding_factor,
+            additional_generator,
+            sk,
+        )
+        .message(b"foo")
+        .build(&secp)
+        .unwrap();
+
+        proof
+            .verify(&secp, commitment, &[], additional_generator)
+            .unwrap();
+        let (opening, _) = proof
+            .rewind(&secp, commitment, sk, &[], additional_generator)
+            .unwrap();
+        assert_eq!(opening.value, value);
+    }
+
+    #[test]
+    fn range_proof_info() {
+        let value = 1_000;
+        let commitment_secrets = CommitmentSecrets::random(value);
+        let tag = Tag::random();
+        let commitment = commitment_secrets.commit(tag);
+
+        let sk = SecretKey::new(&mut thread_rng());
+        let additional_generator =
+            Generator::new_blinded(SECP256K1, tag, commitment_secrets.generator_blinding_factor);
+
+        let proof = RangeProof::new(
+            SECP256K1,
+            1,
+            commitment,
+            value,
+            commitment_secrets.value_blinding_factor,
+            &[],
+            &[],
+            sk,
+            2,
+            20,
+            additional_generator,
+        )
+        .unwrap();
+
+        let info = proof.info();
+        assert_eq!(info.exp, 2);
+        assert_eq!(info.mantissa_bits, 20);
+        assert!(info.min_value <= value && value <= info.max_value);
+
+        let range = proof
+            .verify(SECP256K1, commitment, &[], additional_generator)
+            .unwrap();
+        assert_eq!(range.start, info.min_value);
+        assert_eq!(range.end, info.max_value + 1);
+
+        let parsed = RangeProof::from_slice(&proof.serialize()).unwrap();
+        assert_eq!(parsed.info(), info);
+
+        let exact = RangeProof::new(
+            SECP256K1,
+            value,
+            commitment,
+            value,
+            commitment_secrets.value_blinding_factor,
+            &[],
+            &[],
+            sk,
+            -1,
+            0,
+            additional_generator,
+        )
+        .unwrap();
+        assert_eq!(
+            exact.info(),
+            RangeProofInfo {
+                exp: -1,
+                mantissa_bits: 0,
+                min_value: value,
+                max_value: value,
+            }
+        );
+    }
+
+    #[test]
+    fn range_proof_builder() {
+        let value = 1_000;
+        let commitment_secrets = CommitmentSecrets::random(value);
+        let tag = Tag::random();
+        let commitment = commitment_secrets.commit(tag);
+
+        let sk = SecretKey::new(&mut thread_rng());
+        let additional_generator =
+            Generator::new_blinded(SECP256K1, tag, commitment_secrets.generator_blinding_factor);
+
+        let builder = RangeProofBuilder::confidential(
+            value,
+            commitment,
+            commitment_secrets.value_blinding_factor,
+            additional_generator,
+            sk,
+        )
+        .message(b"foo")
+        .additional_commitment(b"bar");
+        let proof = builder.build(SECP256K1).unwrap();
+
+        assert_eq!(proof.info().exp, 0);
+        assert_eq!(proof.info().mantissa_bits, 52);
+        proof
+            .verify(SECP256K1, commitment, b"bar", additional_generator)
+            .unwrap();
+        let (opening, _) = proof
+            .rewind(SECP256K1, commitment, sk, b"bar", additional_generator)
+            .unwrap();
+        assert!(opening.message.starts_with(b"foo"));
+
+        let exact = RangeProofBuilder::exact_value(
+            value,
+            commitment,
+            commitment_secrets.value_blinding_factor,
+            additional_generator,
+            sk,
+        );
+        let proof = exact.clone().build(SECP256K1).unwrap();
+        assert_eq!(proof.info().min_value, value);
+        assert_eq!(proof.info().max_value, value);
+        proof
+            .verify(SECP256K1, commitment, &[], additional_generator)
+            .unwrap();
+
+        assert_eq!(
+            builder.clone().exp(19).build(SECP256K1).unwrap_err(),
+            Error::InvalidRangeProofExp
+        );
+        assert_eq!(
+            builder.clone().exp(-2).build(SECP256K1).unwrap_err(),
+            Error::InvalidRangeProofExp
+        );
+        assert_eq!(
+            builder.clone().min_bits(65).build(SECP256K1).unwrap_err(),
+            Error::InvalidRangeProofMinBits
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .min_value(1_001)
+                .build(SECP256K1)
+                .unwrap_err(),
+            Error::RangeProofMinValueExceedsValue
+        );
+        assert_eq!(
+            exact.clone().min_bits(52).build(SECP256K1).unwrap_err(),
+            Error::InvalidExactValueRangeProof
+        );
+        assert_eq!(
+            exact.clone().min_value(0).build(SECP256K1).unwrap_err(),
+            Error::InvalidExactValueRangeProof
+        );
+        assert_eq!(
+            exact.message(b"foo").build(SECP256K1).unwrap_err(),
+            Error::InvalidExactValueRangeProof
+        );
+
+        // 52 private bits are 26 radix-4 digits, all but the last carry 128 message bytes.
+        let message = [0xab; 25 * 128 + 1];
+        builder
+            .clone()
+            .message(&message[..25 * 128])
+            .build(SECP256K1)
+            .unwrap();
+        assert_eq!(
+            builder
+                .clone()
+                .message(&message)
+                .build(SECP256K1)
+                .unwrap_err(),
+            Error::RangeProofMessageTooLong
+        );
+        // Without min_bits the digits only cover the 10 bits of the value.
+        let small = builder.clone().min_bits(0);
+        small
+            .clone()
+            .message(&message[..4 * 128])
+            .build(SECP256K1)
+            .unwrap();
+        assert_eq!(
+            small
+                .message(&message[..4 * 128 + 1])
+                .build(SECP256K1)
+                .unwrap_err(),
+            Error::RangeProofMessageTooLong
+        );
+    }
+
+    #[cfg(feature = "bitcoin_hashes")]
+    #[test]
+    fn rewind_range_proof_with_blinding_key() {
+        use ecdh::SharedSecret;
+        use hashes::{sha256, Hash};
+        use PublicKey;
+
+        let value = 1_000;
+        let commitment_secrets = CommitmentSecrets::random(value);
+        let tag = Tag::random();
+        let commitment = commitment_secrets.commit(tag);
+        let additional_generator =
+            Generator::new_blinded(SECP256K1, tag, commitment_secrets.generator_blinding_factor);
+
+        let (blinding_key, blinding_pk) = SECP256K1.generate_keypair(&mut thread_rng());
+        let ephemeral_sk = SecretKey::new(&mut thread_rng());
+        let ephemeral_pk = PublicKey::from_secret_key(SECP256K1, &ephemeral_sk);
+
+        let proof = RangeProof::new_for_output(
+            SECP256K1,
+            1,
+            commitment,
+            value,
+            commitment_secrets.value_blinding_factor,
+            b"foo",
+            b"bar",
+            ephemeral_sk,
+            blinding_pk,
+            0,
+            52,
+            additional_generator,
+        )
+        .unwrap();
+
+        let (opening, _) = proof
+            .rewind_with_blinding_key(
+                SECP256K1,
+                commitment,
+                blinding_key,
+                ephemeral_pk,
+                b"bar",
+                additional_generator,
+            )
+            .unwrap();
+        assert_eq!(opening.value, value);
+        assert_eq!(
+            opening.blinding_factor,
+            commitment_secrets.value_blinding_factor
+        );
+        assert!(opening.message.starts_with(b"foo"));
+
+        // the nonce is the SHA256 of the ECDH shared secret
+        let shared_secret = SharedSecret::new(&ephemeral_pk, &blinding_key);
+        let nonce = sha256::Hash::hash(&shared_secret.secret_bytes());
+        let nonce = SecretKey::from_slice(&nonce[..]).unwrap();
+        let (opening, _) = proof
+            .rewind(SECP256K1, commitment, nonce, b"bar", additional_generator)
+            .unwrap();
+        assert_eq!(opening.value, value);
+
+        let wrong_key = SecretKey::new(&mut thread_rng());
+        assert!(proof
+            .rewind_with_blinding_key(
+                SECP256K1,
+                commitment,
+                wrong_key,
+                ephemeral_pk,
+                b"bar",
+                additional_generator,
+            )
+            .is_err());
+    }
+
+    #[cfg(feature = "bitcoin_hashes")]
+    #[test]
+    fn rewind_range_proof_with_blinding_key_known_answer() {
+        use core::str::FromStr;
+        use PublicKey;
+
+        let value = 12_345_678;
+        let asset = Tag::from([0xaa; 32]);
+        let asset_blinding_factor = Tweak::from_inner([0x33; 32]).unwrap();
+        let value_blinding_factor = Tweak::from_inner([0x44; 32]).unwrap();
+        let generator = Generator::new_blinded(SECP256K1, asset, asset_blinding_factor);
+        let commitment =
+            PedersenCommitment::new(SECP256K1, value, value_blinding_factor, generator);
+
+        let blinding_key = SecretKey::from_slice(&[0x11; 32]).unwrap();
+        let ephemeral_sk = SecretKey::from_slice(&[0x22; 32]).unwrap();
+        let ephemeral_pk = PublicKey::from_secret_key(SECP256K1, &ephemeral_sk);
+        assert_eq!(
+            ephemeral_pk.to_string(),
+            "02466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f27"
+        );
+
+        // SHA256(SHA256(compressed ECDH point)), computed independently of libsecp256k1
+        let nonce =
+            SecretKey::from_str("f035251054f3040b8ae6c87040d1d562e857ed688f6818433375f21c13df0054")
+                .unwrap();
+        let message = RangeProofMessage::new(asset, asset_blinding_factor).serialize();
+        let proof = RangeProof::new(
+            SECP256K1,
+            1,
+            commitment,
+            value,
+            value_blinding_factor,
+            &message,
+            &[],
+            nonce,
+            0,
+            52,
+            generator,
+        )
+        .unwrap();
+
+        let (opening, range) = proof
+            .rewind_with_blinding_key(
+                SECP256K1,
+                commitment,
+                blinding_key,
+                ephemeral_pk,
+                &[],
+                generator,
+            )
+            .unwrap();
+        assert_eq!(opening.value, value);
+        assert_eq!(opening.blinding_factor, value_blinding_factor);
+        assert_eq!(range.start, 1);
+        let message = opening.asset_message().unwrap();
+        assert_eq!(message.asset, asset);
+        assert_eq!(message.asset_blinding_factor, asset_blinding_factor);
+    }
+
+    #[test]
+    fn rewind_asset_message() {
+        let value = 1_000;
+        let commitment_secrets = CommitmentSecrets::random(value);
+        let tag = Tag::random();
+        let commitment = commitment_secrets.commit(tag);
+        let additional_generator =
+            Generator::new_blinded(SECP256K1, tag, commitment_secrets.generator_blinding_factor);
+        let sk = SecretKey::new(&mut thread_rng());
+
+        let message = RangeProofMessage::new(tag, commitment_secrets.generator_blinding_factor);
+        let serialized = message.serialize();
+        assert_eq!(RangeProofMessage::from_slice(&serialized).unwrap(), message);
+
+        let proof = RangeProofBuilder::confidential(
+            value,
+            commitment,
+            commitment_secrets.value_blinding_factor,
+            additional_generator,
+            sk,
+        )
+        .message(&serialized)
+        .build(SECP256K1)
+        .unwrap();
+
+        let (opening, _) = proof
+            .rewind(SECP256K1, commitment, sk, &[], additional_generator)
+            .unwrap();
+        assert_eq!(opening.asset_message().unwrap(), message);
+
+        let short = Opening {
+            value,
+            blinding_factor: commitment_secrets.value_blinding_factor,
+            message: serialized[..63].into(),
+        };
+        assert_eq!(
+            short.asset_message().unwrap_err(),
+            Error::InvalidRangeProofMessage
+        );
+
+        let mut trailing = serialized.to_vec();
+        trailing.push(1);
+        let trailing = Opening {
+            value,
+            blinding_factor: commitment_secrets.value_blinding_factor,
+            message: trailing.into(),
+        };
+        assert_eq!(
+            trailing.asset_message().unwrap_err(),
+            Error::InvalidRangeProofMessage
+        );
+
+        let mut invalid_blinder = serialized;
+        invalid_blinder[32..].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+            RangeProofMessage::from_slice(&invalid_blinder).unwrap_err(),
+            Error::InvalidRangeProofMessage
+        );
     }
 
     #[test]
@@ -358,4 +1276,67 @@ mod tests {
             .message
             .ends_with(&vec![0; opening.message.len() - message.len()]));
     }
+
+    #[test]
+    fn rewind_range_proof_into_buffer() {
+        let value = 1_000;
+        let commitment_secrets = CommitmentSecrets::random(value);
+        let tag = Tag::random();
+        let commitment = commitment_secrets.commit(tag);
+        let sk = SecretKey::new(&mut thread_rng());
+        let additional_generator =
+            Generator::new_blinded(SECP256K1, tag, commitment_secrets.generator_blinding_factor);
+
+        let proof = RangeProof::new(
+            SECP256K1,
+            1,
+            commitment,
+            value,
+            commitment_secrets.value_blinding_factor,
+            b"foo",
+            b"bar",
+            sk,
+            0,
+            52,
+            additional_generator,
+        )
+        .unwrap();
+
+        let (opening, range) = proof
+            .rewind(SECP256K1, commitment, sk, b"bar", additional_generator)
+            .unwrap();
+
+        let mut buf = [0u8; RangeProof::MAX_MESSAGE_LEN];
+        for _ in 0..2 {
+            let (opening_ref, range_ref) = proof
+                .rewind_into(
+                    SECP256K1,
+                    commitment,
+                    sk,
+                    b"bar",
+                    additional_generator,
+                    &mut buf,
+                )
+                .unwrap();
+            assert_eq!(opening_ref.value, opening.value);
+            assert_eq!(opening_ref.blinding_factor, opening.blinding_factor);
+            assert_eq!(opening_ref.message, &opening.message[..]);
+            assert_eq!(range_ref, range);
+        }
+
+        // a short buffer receives a truncated message
+        let mut short_buf = [0u8; 2];
+        let (opening_ref, _) = proof
+            .rewind_into(
+                SECP256K1,
+                commitment,
+                sk,
+                b"bar",
+                additional_generator,
+                &mut short_buf,
+            )
+            .unwrap();
+        assert_eq!(opening_ref.message, b"fo");
+        assert_eq!(opening_ref.value, value);
+    }
 }
@@ -1,9 +1,15 @@
+#[cfg(any(feature = "std", feature = "alloc"))]
+use alloc::vec::Vec;
 use core::mem::size_of;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::str;
 use ffi;
+#[cfg(any(feature = "std", feature = "alloc"))]
 use from_hex;
-use std::str;
 use Verification;
 use {Error, Generator, Secp256k1};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use {Signing, Tag, Tweak};
 
 /// Represents a surjection proof.
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
@@ -11,17 +17,18 @@ pub struct SurjectionProof {
     inner: ffi::SurjectionProof,
 }
 
-#[cfg(feature = "rand")]
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "rand"))]
 mod with_rand {
     use super::*;
     use rand::Rng;
-    use {Signing, Tag, Tweak};
 
     impl SurjectionProof {
         /// Prove that a given tag - when blinded - is contained within another set of blinded tags.
         ///
         /// Mathematically, we are proving that there exists a surjective mapping between the domain and codomain of tags.
         /// Blinding a tag produces a [`Generator`]. As such, to create this proof we need to provide the `[Generator]`s and the respective blinding factors that were used to create them.
+        ///
+        /// This draws the seed for input selection from `rng`, see [`SurjectionProof::new_with_seed`].
         pub fn new<C: Signing, R: Rng>(
             secp: &Secp256k1<C>,
             rng: &mut R,
@@ -29,72 +36,224 @@ mod with_rand {
             codomain_blinding_factor: Tweak,
             domain: &[(Generator, Tag, Tweak)],
         ) -> Result<SurjectionProof, Error> {
-            let mut proof = ffi::SurjectionProof::new();
-
             let mut seed = [0u8; 32];
             rng.fill_bytes(&mut seed);
 
-            let mut domain_index = 0;
-            let max_iteration = 100;
-
-            let mut domain_blinded_tags = Vec::with_capacity(domain.len());
-            let mut domain_tags = Vec::with_capacity(domain.len());
-            let mut domain_blinding_factors = Vec::with_capacity(domain.len());
-
-            for (blinded_tag, tag, bf) in domain {
-                domain_blinded_tags.push(*blinded_tag.as_inner());
-                domain_tags.push(tag.into_inner());
-                domain_blinding_factors.push(*bf);
-            }
-
-            let ret = unsafe {
-                ffi::secp256k1_surjectionproof_initialize(
-                    *secp.ctx(),
-                    &mut proof,
-                    &mut domain_index,
-                    domain_tags.as_ptr(),
-                    domain.len(),
-                    domain.len().min(3),
-                    codomain_tag.as_inner(),
-                    max_iteration,
-                    seed.as_ptr(),
-                )
-            };
-
-            if ret == 0 {
-                return Err(Error::CannotProveSurjection);
-            }
-
-            let codomain_blinded_tag =
-                Generator::new_blinded(secp, codomain_tag, codomain_blinding_factor);
-
-            let ret = unsafe {
-                ffi::secp256k1_surjectionproof_generate(
-                    *secp.ctx(),
-                    &mut proof,
-                    domain_blinded_tags.as_ptr(),
-                    domain.len(),
-                    codomain_blinded_tag.as_inner(),
-                    domain_index,
-                    domain
-                        .get(domain_index)
-                        .ok_or(Error::CannotProveSurjection)?
-                        .2
-                        .as_ptr(), // TODO: Return dedicated error here?
-                    codomain_blinding_factor.as_ptr(),
-                )
-            };
-
-            if ret == 0 {
-                return Err(Error::CannotProveSurjection);
-            }
-
-            Ok(SurjectionProof { inner: proof })
+            SurjectionProof::new_with_seed(
+                secp,
+                seed,
+                codomain_tag,
+                codomain_blinding_factor,
+                domain,
+            )
+        }
+    }
+}
+
+/// Reusable buffers for creating surjection proofs.
+///
+/// [`SurjectionProofBuilder::build_into`] copies the domain into these buffers, which keep their
+/// capacity between calls. Reusing one scratch object for many proofs avoids allocating per proof.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Default)]
+pub struct ProofScratch {
+    domain_tags: Vec<ffi::Tag>,
+    domain_blinded_tags: Vec<ffi::PublicKey>,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl ProofScratch {
+    /// Creates empty scratch space.
+    pub fn new() -> ProofScratch {
+        ProofScratch::default()
+    }
+
+    /// Creates scratch space for domains of up to `n_inputs` inputs.
+    pub fn with_capacity(n_inputs: usize) -> ProofScratch {
+        ProofScratch {
+            domain_tags: Vec::with_capacity(n_inputs),
+            domain_blinded_tags: Vec::with_capacity(n_inputs),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl ::core::fmt::Debug for ProofScratch {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.debug_struct("ProofScratch")
+            .field("capacity", &self.domain_tags.capacity())
+            .finish()
+    }
+}
+
+/// Creates a [`SurjectionProof`] with explicit control over the input selection.
+///
+/// The inputs to be used in the proof are selected pseudo-randomly from the domain based on the
+/// given seed. Using the same seed and domain produces byte-for-byte identical proofs.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[derive(Debug, Clone)]
+pub struct SurjectionProofBuilder {
+    seed: [u8; 32],
+    n_inputs_to_use: Option<usize>,
+    max_iterations: usize,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl SurjectionProofBuilder {
+    /// Creates a new builder using the given seed for input selection.
+    ///
+    /// By default, `min(3, domain.len())` inputs are used and input selection is retried at most 100 times.
+    pub fn new(seed: [u8; 32]) -> SurjectionProofBuilder {
+        SurjectionProofBuilder {
+            seed,
+            n_inputs_to_use: None,
+            max_iterations: 100,
+        }
+    }
+
+    /// Sets the number of inputs of the domain to use in the proof, i.e. the size of the anonymity set.
+    ///
+    /// Must be at least 1 and at most the size of the domain.
+    pub fn n_inputs_to_use(mut self, n_inputs_to_use: usize) -> SurjectionProofBuilder {
+        self.n_inputs_to_use = Some(n_inputs_to_use);
+        self
+    }
+
+    /// Sets the maximum number of times a random set of inputs is drawn until one matching the
+    /// codomain is found.
+    ///
+    /// Must be at least 1.
+    pub fn max_iterations(mut self, max_iterations: usize) -> SurjectionProofBuilder {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Creates the surjection proof.
+    ///
+    /// Returns the proof together with the index of the domain element that was mapped to the codomain.
+    pub fn build<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        codomain_tag: Tag,
+        codomain_blinding_factor: Tweak,
+        domain: &[(Generator, Tag, Tweak)],
+    ) -> Result<(SurjectionProof, usize), Error> {
+        self.build_into(
+            secp,
+            codomain_tag,
+            codomain_blinding_factor,
+            domain,
+            &mut ProofScratch::new(),
+        )
+    }
+
+    /// Same as [`SurjectionProofBuilder::build`], but uses the buffers of `scratch` instead of
+    /// allocating new ones.
+    pub fn build_into<C: Signing>(
+        &self,
+        secp: &Secp256k1<C>,
+        codomain_tag: Tag,
+        codomain_blinding_factor: Tweak,
+        domain: &[(Generator, Tag, Tweak)],
+        scratch: &mut ProofScratch,
+    ) -> Result<(SurjectionProof, usize), Error> {
+        let n_inputs_to_use = self.n_inputs_to_use.unwrap_or_else(|| domain.len().min(3));
+
+        if domain.is_empty()
+            || domain.len() > ffi::SURJECTIONPROOF_MAX_N_INPUTS
+            || n_inputs_to_use == 0
+            || n_inputs_to_use > domain.len()
+            || n_inputs_to_use > ffi::SURJECTIONPROOF_MAX_USED_INPUTS
+            || self.max_iterations == 0
+        {
+            return Err(Error::InvalidSurjectionProofParameters);
+        }
+
+        if !domain.iter().any(|(_, tag, _)| *tag == codomain_tag) {
+            return Err(Error::SurjectionProofNoMatchingInput);
+        }
+
+        let mut proof = ffi::SurjectionProof::new();
+        let mut domain_index = 0;
+
+        let domain_blinded_tags = &mut scratch.domain_blinded_tags;
+        let domain_tags = &mut scratch.domain_tags;
+        domain_blinded_tags.clear();
+        domain_tags.clear();
+
+        for (blinded_tag, tag, _) in domain {
+            domain_blinded_tags.push(*blinded_tag.as_inner());
+            domain_tags.push(tag.into_inner());
+        }
+
+        let ret = unsafe {
+            ffi::secp256k1_surjectionproof_initialize(
+                *secp.ctx(),
+                &mut proof,
+                &mut domain_index,
+                domain_tags.as_ptr(),
+                domain.len(),
+                n_inputs_to_use,
+                codomain_tag.as_inner(),
+                self.max_iterations,
+                self.seed.as_ptr(),
+            )
+        };
+
+        if ret == 0 {
+            return Err(Error::SurjectionProofIterationLimit);
         }
+
+        let codomain_blinded_tag =
+            Generator::new_blinded(secp, codomain_tag, codomain_blinding_factor);
+
+        let ret = unsafe {
+            ffi::secp256k1_surjectionproof_generate(
+                *secp.ctx(),
+                &mut proof,
+                domain_blinded_tags.as_ptr(),
+                domain.len(),
+                codomain_blinded_tag.as_inner(),
+                domain_index,
+                domain[domain_index].2.as_ptr(),
+                codomain_blinding_factor.as_ptr(),
+            )
+        };
+
+        if ret == 0 {
+            return Err(Error::CannotProveSurjection);
+        }
+
+        Ok((SurjectionProof { inner: proof }, domain_index))
     }
 }
 
 impl SurjectionProof {
+    /// Prove that a given tag - when blinded - is contained within another set of blinded tags.
+    ///
+    /// Same as [`SurjectionProof::new`] but takes the 32-byte seed for input selection directly
+    /// instead of drawing it from a random number generator. The seed should be random; reusing it
+    /// for the same domain selects the same inputs.
+    ///
+    /// Use [`SurjectionProofBuilder`] to control the number of used inputs and the number of iterations.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn new_with_seed<C: Signing>(
+        secp: &Secp256k1<C>,
+        seed: [u8; 32],
+        codomain_tag: Tag,
+        codomain_blinding_factor: Tweak,
+        domain: &[(Generator, Tag, Tweak)],
+    ) -> Result<SurjectionProof, Error> {
+        let (proof, _) = SurjectionProofBuilder::new(seed).build(
+            secp,
+            codomain_tag,
+            codomain_blinding_factor,
+            domain,
+        )?;
+
+        Ok(proof)
+    }
+
     /// Creates a surjection proof from a slice of bytes.
     pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
         let mut proof = ffi::SurjectionProof::new();
@@ -118,6 +277,7 @@ impl SurjectionProof {
     /// Serializes a surjection proof.
     ///
     /// The format of this serialization is stable and platform-independent.
+    #[cfg(any(feature = "std", feature = "alloc"))]
     pub fn serialize(&self) -> Vec<u8> {
         let mut size = unsafe {
             ffi::secp256k1_surjectionproof_serialized_size(
@@ -151,7 +311,40 @@ impl SurjectionProof {
         }
     }
 
+    /// Returns the total number of inputs (the size of the domain) the proof expects.
+    pub fn n_total_inputs(&self) -> usize {
+        unsafe {
+            ffi::secp256k1_surjectionproof_n_total_inputs(
+                ffi::secp256k1_context_no_precomp,
+                &self.inner,
+            )
+        }
+    }
+
+    /// Returns the number of inputs that are actually used in the proof.
+    pub fn n_used_inputs(&self) -> usize {
+        unsafe {
+            ffi::secp256k1_surjectionproof_n_used_inputs(
+                ffi::secp256k1_context_no_precomp,
+                &self.inner,
+            )
+        }
+    }
+
+    /// Decodes the bitmap of used inputs.
+    ///
+    /// The returned vector has [`SurjectionProof::n_total_inputs`] entries, the entry at index `i` is
+    /// `true` if the `i`-th input of the domain is used in the proof.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn used_inputs(&self) -> Vec<bool> {
+        (0..self.n_total_inputs())
+            .map(|i| self.inner.used_inputs[i / 8] & (1 << (i % 8)) != 0)
+            .collect()
+    }
+
     /// Verify a surjection proof.
+    ///
+    /// Verification doesn't allocate.
     #[must_use]
     pub fn verify<C: Verification>(
         &self,
@@ -180,7 +373,7 @@ impl SurjectionProof {
     }
 }
 
-#[cfg(feature = "bitcoin_hashes")]
+#[cfg(all(any(feature = "std", feature = "alloc"), feature = "bitcoin_hashes"))]
 impl ::core::fmt::Display for SurjectionProof {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
         use hashes::hex::format_hex;
@@ -189,6 +382,7 @@ impl ::core::fmt::Display for SurjectionProof {
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl str::FromStr for SurjectionProof {
     type Err = Error;
     fn from_str(s: &str) -> Result<SurjectionProof, Error> {
@@ -200,7 +394,11 @@ impl str::FromStr for SurjectionProof {
     }
 }
 
-#[cfg(all(feature = "serde", feature = "bitcoin_hashes"))]
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    feature = "serde",
+    feature = "bitcoin_hashes"
+))]
 impl ::serde::Serialize for SurjectionProof {
     fn serialize<S: ::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
         if s.is_human_readable() {
@@ -211,7 +409,11 @@ impl ::serde::Serialize for SurjectionProof {
     }
 }
 
-#[cfg(all(feature = "serde", feature = "bitcoin_hashes"))]
+#[cfg(all(
+    any(feature = "std", feature = "alloc"),
+    feature = "serde",
+    feature = "bitcoin_hashes"
+))]
 impl<'de> ::serde::Deserialize<'de> for SurjectionProof {
     fn deserialize<D: ::serde::Deserializer<'de>>(d: D) -> Result<SurjectionProof, D::Error> {
         use serde_util;
@@ -230,8 +432,9 @@ impl<'de> ::serde::Deserialize<'de> for SurjectionProof {
 #[cfg(all(test, feature = "global-context"))] // use global context for convenience
 mod tests {
     use super::*;
+    use ffi::types::AlignedType;
     use rand::thread_rng;
-    use {Tag, Tweak, SECP256K1};
+    use SECP256K1;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test as test;
@@ -271,6 +474,34 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn test_surjection_proof_with_preallocated_context() {
+        let mut buf = [AlignedType::ZERO; 64];
+        let secp = Secp256k1::preallocated_new(&mut buf).unwrap();
+
+        let (domain_tag_1, domain_blinded_tag_1, domain_bf_1) = random_blinded_tag();
+        let (domain_tag_2, domain_blinded_tag_2, domain_bf_2) = random_blinded_tag();
+        let (codomain_blinded_tag, codomain_bf) = blind_tag(domain_tag_2);
+
+        let proof = SurjectionProof::new_with_seed(
+            &secp,
+            [7; 32],
+            domain_tag_2,
+            codomain_bf,
+            &[
+                (domain_blinded_tag_1, domain_tag_1, domain_bf_1),
+                (domain_blinded_tag_2, domain_tag_2, domain_bf_2),
+            ],
+        )
+        .unwrap();
+
+        assert!(proof.verify(
+            &secp,
+            codomain_blinded_tag,
+            &[domain_blinded_tag_1, domain_blinded_tag_2],
+        ))
+    }
+
     #[test]
     fn test_serialize_and_parse_surjection_proof() {
         let (domain_tag_1, domain_blinded_tag_1, domain_bf_1) = random_blinded_tag();
@@ -291,6 +522,157 @@ mod tests {
         assert_eq!(parsed, proof)
     }
 
+    #[test]
+    fn test_surjection_proof_used_inputs() {
+        let domain = (0..5)
+            .map(|_| {
+                let (tag, blinded_tag, bf) = random_blinded_tag();
+                (blinded_tag, tag, bf)
+            })
+            .collect::<Vec<_>>();
+        let codomain_tag = domain[3].1;
+        let (_, codomain_bf) = blind_tag(codomain_tag);
+
+        let proof = SurjectionProof::new(
+            SECP256K1,
+            &mut thread_rng(),
+            codomain_tag,
+            codomain_bf,
+            &domain,
+        )
+        .unwrap();
+
+        assert_eq!(proof.n_total_inputs(), 5);
+        assert_eq!(proof.n_used_inputs(), 3);
+
+        let used_inputs = proof.used_inputs();
+        assert_eq!(used_inputs.len(), 5);
+        assert_eq!(used_inputs.iter().filter(|used| **used).count(), 3);
+        assert!(used_inputs[3]);
+
+        let parsed = SurjectionProof::from_slice(&proof.serialize()).unwrap();
+        assert_eq!(parsed.n_total_inputs(), 5);
+        assert_eq!(parsed.n_used_inputs(), 3);
+        assert_eq!(parsed.used_inputs(), used_inputs);
+    }
+
+    #[test]
+    fn test_surjection_proof_builder() {
+        let domain = (0..10)
+            .map(|_| {
+                let (tag, blinded_tag, bf) = random_blinded_tag();
+                (blinded_tag, tag, bf)
+            })
+            .collect::<Vec<_>>();
+        let domain_blinded_tags = domain.iter().map(|d| d.0).collect::<Vec<_>>();
+        let codomain_tag = domain[7].1;
+        let (codomain_blinded_tag, codomain_bf) = blind_tag(codomain_tag);
+
+        let builder = SurjectionProofBuilder::new([42; 32])
+            .n_inputs_to_use(5)
+            .max_iterations(1000);
+        let (proof, domain_index) = builder
+            .build(SECP256K1, codomain_tag, codomain_bf, &domain)
+            .unwrap();
+
+        assert_eq!(domain_index, 7);
+        assert_eq!(proof.n_total_inputs(), 10);
+        assert_eq!(proof.n_used_inputs(), 5);
+        assert!(proof.verify(SECP256K1, codomain_blinded_tag, &domain_blinded_tags));
+
+        // the same seed yields the same proof
+        let (same_proof, _) = builder
+            .build(SECP256K1, codomain_tag, codomain_bf, &domain)
+            .unwrap();
+        assert_eq!(proof.serialize(), same_proof.serialize());
+    }
+
+    #[test]
+    fn test_surjection_proof_builder_with_scratch() {
+        let builder = SurjectionProofBuilder::new([42; 32]);
+        let mut scratch = ProofScratch::with_capacity(3);
+
+        for n_inputs in &[3, 5, 1] {
+            let domain = (0..*n_inputs)
+                .map(|_| {
+                    let (tag, blinded_tag, bf) = random_blinded_tag();
+                    (blinded_tag, tag, bf)
+                })
+                .collect::<Vec<_>>();
+            let domain_blinded_tags = domain.iter().map(|d| d.0).collect::<Vec<_>>();
+            let codomain_tag = domain[0].1;
+            let (codomain_blinded_tag, codomain_bf) = blind_tag(codomain_tag);
+
+            let (proof, _) = builder
+                .build_into(SECP256K1, codomain_tag, codomain_bf, &domain, &mut scratch)
+                .unwrap();
+            assert!(proof.verify(SECP256K1, codomain_blinded_tag, &domain_blinded_tags));
+
+            let (same_proof, _) = builder
+                .build(SECP256K1, codomain_tag, codomain_bf, &domain)
+                .unwrap();
+            assert_eq!(proof, same_proof);
+        }
+    }
+
+    #[test]
+    fn test_surjection_proof_builder_errors() {
+        let domain = (0..10)
+            .map(|_| {
+                let (tag, blinded_tag, bf) = random_blinded_tag();
+                (blinded_tag, tag, bf)
+            })
+            .collect::<Vec<_>>();
+        let codomain_tag = domain[7].1;
+        let (_, codomain_bf) = blind_tag(codomain_tag);
+
+        let (other_tag, _, other_bf) = random_blinded_tag();
+        assert_eq!(
+            SurjectionProofBuilder::new([42; 32])
+                .build(SECP256K1, other_tag, other_bf, &domain)
+                .unwrap_err(),
+            Error::SurjectionProofNoMatchingInput
+        );
+
+        // a single input out of ten is selected in a single iteration, find a seed that fails
+        let builder = (0u8..=255)
+            .map(|i| {
+                SurjectionProofBuilder::new([i; 32])
+                    .n_inputs_to_use(1)
+                    .max_iterations(1)
+            })
+            .find(|b| {
+                b.build(SECP256K1, codomain_tag, codomain_bf, &domain)
+                    .is_err()
+            })
+            .unwrap();
+        assert_eq!(
+            builder
+                .build(SECP256K1, codomain_tag, codomain_bf, &domain)
+                .unwrap_err(),
+            Error::SurjectionProofIterationLimit
+        );
+
+        for builder in &[
+            SurjectionProofBuilder::new([42; 32]).n_inputs_to_use(0),
+            SurjectionProofBuilder::new([42; 32]).n_inputs_to_use(11),
+            SurjectionProofBuilder::new([42; 32]).max_iterations(0),
+        ] {
+            assert_eq!(
+                builder
+                    .build(SECP256K1, codomain_tag, codomain_bf, &domain)
+                    .unwrap_err(),
+                Error::InvalidSurjectionProofParameters
+            );
+        }
+        assert_eq!(
+            SurjectionProofBuilder::new([42; 32])
+                .build(SECP256K1, codomain_tag, codomain_bf, &[])
+                .unwrap_err(),
+            Error::InvalidSurjectionProofParameters
+        );
+    }
+
     fn random_blinded_tag() -> (Tag, Generator, Tweak) {
         let tag = Tag::random();
 
@@ -306,3 +688,43 @@ mod tests {
         (blinded_tag, bf)
     }
 }
+
+#[cfg(test)] // does not depend on `rand`
+mod seed_tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn test_create_surjection_proof_with_seed() {
+        let secp = Secp256k1::new();
+        let blinded_tag = |tag: u8, bf: u8| {
+            let tag = Tag::from([tag; 32]);
+            let bf = Tweak::from_inner([bf; 32]).unwrap();
+            (Generator::new_blinded(&secp, tag, bf), tag, bf)
+        };
+        let domain = [blinded_tag(1, 10), blinded_tag(2, 20), blinded_tag(3, 30)];
+        let (codomain_blinded_tag, codomain_tag, codomain_bf) = blinded_tag(2, 40);
+        let domain_blinded_tags = [domain[0].0, domain[1].0, domain[2].0];
+
+        let proof =
+            SurjectionProof::new_with_seed(&secp, [1; 32], codomain_tag, codomain_bf, &domain)
+                .unwrap();
+
+        assert!(proof.verify(&secp, codomain_blinded_tag, &domain_blinded_tags));
+        assert_eq!(
+            proof,
+            SurjectionProof::new_with_seed(&secp, [1; 32], codomain_tag, codomain_bf, &domain)
+                .unwrap()
+        );
+        assert!(!proof.verify(&secp, domain[0].0, &domain_blinded_tags));
+
+        let (_, unknown_tag, unknown_bf) = blinded_tag(4, 40);
+        assert_eq!(
+            SurjectionProof::new_with_seed(&secp, [1; 32], unknown_tag, unknown_bf, &domain)
+                .unwrap_err(),
+            Error::SurjectionProofNoMatchingInput
+        );
+    }
+}
@@ -12,7 +12,7 @@ impl Tag {
         self.0
     }
 
-    #[cfg(all(feature = "rand", feature = "std"))]
+    #[cfg(any(feature = "std", feature = "alloc"))]
     pub(crate) fn as_inner(&self) -> &ffi::Tag {
         &self.0
     }